@@ -25,13 +25,13 @@ mod scope {
     // We can use `cargo expand` to show code expanded by `TypedBuilder`,
     // copy the generated `__build` method, and modify the content of the build method.
     #[allow(non_camel_case_types)]
-    impl<__z: FooBuilder_Optional<i32>, __y: FooBuilder_Optional<Option<i32>>> FooBuilder<((i32,), __y, __z)> {
+    impl<__z: typed_builder::Optional<i32>, __y: typed_builder::Optional<Option<i32>>> FooBuilder<((i32,), __y, __z)> {
         pub fn build(self) -> Bar {
-            let foo = self.__build();
+            let built = self.__build();
             Bar {
-                x: foo.x + 1,
-                y: foo.y.map(|y| y + 1),
-                z: foo.z + 1,
+                x: built.x + 1,
+                y: built.y.map(|y| y + 1),
+                z: built.z + 1,
             }
         }
     }
@@ -1,5 +1,8 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 /// `TypedBuilder` is not a real type - deriving it will generate a `::builder()` method on your
 /// struct that will return a compile-time checked builder. Set the fields using setters with the
 /// same name as the struct's fields and call `.build()` when you are done to create your object.
@@ -50,11 +53,86 @@
 /// // Foo::builder().x(1).y(2).y(3);
 /// ```
 ///
+/// Calling `build()` while a required field is missing picks a "fake" `build` method that is
+/// deprecated with the note `Missing required field x`, and which requires an argument of the
+/// uninhabited type `FooBuilder_Error_Missing_required_field_x`, so both the warning and the error
+/// name the missing field. When several fields are missing, the first one (in declaration order)
+/// is reported. Setting a field twice is reported the same way, with `Repeated field y`.
+///
+/// The fields of the builder type are private, so outside of the module that declares the struct
+/// the builder can only be created with `Foo::builder()` and changed with the generated methods,
+/// which keeps it from getting into states those methods wouldn't produce. (Code in the module
+/// itself can see private fields, like with any other struct declared there.)
+///
+/// Once all the required fields are set, the builder can also be converted into the struct with
+/// `From`/`Into`, e.g. `let foo: Foo = Foo::builder().x(1).into();`. The conversion calls the build
+/// method, so when it can fail (because of `validate` or `group`), `TryFrom`/`TryInto` is
+/// implemented instead, with the same error - e.g.
+/// `let foo: Foo = Foo::builder().x(1).try_into()?;`. It is not implemented when the build method
+/// doesn't produce the struct (with `build_method(into = ...)` or `build_as_tuple`), and
+/// `build_method(no_from)` leaves it out.
+///
+/// A `#[deprecated]` attribute on a field is copied to the field's setters (including the `each`
+/// setter), so setting the field warns the same way accessing it does. Other attributes on the
+/// field are not copied.
+///
+/// Structs without fields - including unit structs - get a builder as well, whose `build()` can
+/// be called right away.
+///
+/// Tuple structs are supported as well. Their setters are named after the field's position, with
+/// a leading underscore - `_0`, `_1` and so on:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, TypedBuilder)]
+/// struct Point(i32, #[builder(default)] i32);
+///
+/// assert!(Point::builder()._0(1).build() == Point(1, 0));
+/// ```
+///
+/// On enums, each variant gets a builder of its own, created by a method named after the variant.
+/// Only variants with named fields are supported:
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, TypedBuilder)]
+/// enum Shape {
+///     Circle { radius: f64 },
+///     #[builder(builder_type(name = RectBuilder))]
+///     Rectangle { width: f64, #[builder(default = width)] height: f64 },
+/// }
+///
+/// assert!(Shape::circle_builder().radius(1.0).build() == Shape::Circle { radius: 1.0 });
+/// let square: RectBuilder<((f64,), ())> = Shape::rectangle_builder().width(2.0);
+/// assert!(square.build() == Shape::Rectangle { width: 2.0, height: 2.0 });
+/// ```
+///
+/// The builder of a variant is named after the enum and the variant (`ShapeCircleBuilder`), and
+/// the `#[builder(...)]` attributes of the variant apply on top of those of the enum.
+/// `into_builder`, `field_metadata` and `expose_defaults` are not supported for enums, and the
+/// `name` of `builder_method(...)` and `builder_type(...)` can only be set on the variants.
+///
+/// Fields can be gated with `#[cfg(...)]`. The compiler removes disabled fields before deriving, so
+/// the builder only has setters for the fields that exist in the current configuration.
+/// `#[cfg_attr(..., builder(...))]` works the same way.
+///
+/// Deriving on a `#[non_exhaustive]` struct (or for `#[non_exhaustive]` enum variants) works as
+/// usual. The build method constructs the value in the defining crate, so other crates can still
+/// create it through the builder even though they can't use a struct literal - which makes the
+/// builder a convenient way to add fields without breaking them, as long as the new fields have a
+/// default.
+///
 /// # Customization with attributes
 ///
 /// In addition to putting `#[derive(TypedBuilder)]` on a type, you can specify a `#[builder(…)]`
 /// attribute on the type, and on any fields in it.
 ///
+/// The settings can be split across several `#[builder(…)]` attributes on the same type or field.
+/// Lists (such as `derive(...)` and `attr(...)`) accumulate, while giving a setting that takes a
+/// single value (such as `name = ...`) more than once is an error.
+///
 /// On the **type**, the following values are permitted:
 ///
 /// - `doc`: enable documentation of the builder type. By default, the builder type is given
@@ -62,16 +140,28 @@
 ///   but it won't be a link. If you turn this on, the builder type and its `build` method will get
 ///   sane defaults. The field methods on the builder will be undocumented by default.
 ///
+/// - `doc_examples`: like `doc`, and also add a usage example to the docs of the builder type,
+///   which calls the setters of all the fields (marking the optional ones) and then the build
+///   method. The example is marked `ignore`, so it is not run as a doctest.
+///
 /// - The following subsections:
 ///   - `builder_method(...)`: customize the builder method that creates the builder type
 ///   - `builder_type(...)`: customize the builder type
 ///   - `build_method(...)`: customize the final build method
 ///
 ///   All have the same fields:
-///   - `vis = "…"`: sets the visibility of the build method, default is `pub`
-///   - `name = …`: sets the fn name of the build method, default is `build`
+///   - `vis = "…"`: sets the visibility of the method or type. The default is the visibility of
+///     the struct, except for the build method, which defaults to the visibility of the builder
+///     type. Mutators have the visibility of the builder type as well.
+///   - `name = …`: sets the fn name of the build method, default is `build`. Can be given either
+///     as an identifier or as a string, e.g. `builder_method(name = "configure")`
 ///   - `doc = "…"` replaces the default documentation that will be generated for the
 ///     `build()` method of the builder type. Setting this implies `doc`.
+///   - `attr(...)`: puts the attributes inside of it on the generated declaration as-is. For
+///     example, `builder_type(attr(derive(PartialEq), repr(C)))` puts `#[derive(PartialEq)]`
+///     and `#[repr(C)]` on the builder type. Validation failures are returned as a `Result`
+///     rather than panicking, so the build method has no `#[track_caller]` by default. If a
+///     `default` may panic, `build_method(attr(track_caller))` adds it.
 ///
 ///
 /// - The `build_method(...)` subsection also has:
@@ -80,6 +170,17 @@
 ///     type is set, but `into` is specified, the return type will be generic and the user can
 ///     decide which type shall be constructed. In both cases an [`Into`] conversion is required to
 ///     be defined from the original type to the target type.
+///   - `no_from`: don't implement `From` (or `TryFrom`) for converting the builder into the struct
+///     (see above) - e.g. when the build method is private, since the conversion is as visible as
+///     the builder type, or to implement the conversion yourself.
+///   - `error = ...`: the name of the error type that is generated when the build method returns a
+///     `Result` (see `build_result`), given either as an identifier or as a string. Defaults to
+///     the builder's name followed by `Error`, e.g. `FooBuilderError`, which may already be taken
+///     in the module.
+///   - `bounds(...)`: where predicates that only apply to the build method (and to the conversions
+///     of the builder into the struct), e.g. `build_method(bounds(T: Clone))`. The setters don't
+///     require them, so the fields can be set with values that don't satisfy them, but the struct
+///     can only be built if they are satisfied. Defaults can rely on these bounds.
 ///
 /// - `field_defaults(...)` is structured like the `#[builder(...)]` attribute you can put on the
 ///   fields and sets default options for fields of the type. If specific field need to revert some
@@ -112,44 +213,478 @@
 ///    struct Point { x: f32, y: f32 }
 ///    ```
 ///
+/// - `builder_impl_default`: implement `Default` for the builder type in its initial state, so
+///   that `FooBuilder::default()` can be used instead of `Foo::builder()`.
+///
+/// - `derive(...)`: implement traits for the builder type. `Clone`, `Debug`, `PartialEq`, `Eq` and
+///   `Hash` are supported, and each is implemented for builders whose field values all implement
+///   it - so e.g. two builders can be compared when the values set in them can. The builder is
+///   always `Clone`, so `derive(Clone)` is accepted but has no effect.
+///
+/// - `inline`: mark the setters and the build method with `#[inline]`, allowing them to be
+///   inlined across crates.
+///
+/// - `into_builder`: generate an `into_builder(self)` method on the struct, which returns a builder
+///   with all of its fields already set. Setters can't be called on fields that were already set
+///   (they report a repeated field), except for `setter(mutable)` ones - so the values are changed
+///   with mutators or mutable setters before building again. Skipped fields are not stored in the
+///   builder, so they are recomputed by the build method. The fields are moved out of the struct,
+///   so a struct that implements `Drop` can't have `into_builder`.
+///
+/// - `introspect`: generate a `field_states(&self)` method on the builder, for debugging. It
+///   returns an array with the name of each field that has a setter, paired with whether the field
+///   was set. Which fields are set is known at compile time (it is part of the builder's type), so
+///   this does not keep any extra state at runtime. Fields with a `mutable` setter are reported as
+///   set once their setter was called.
+///
+/// - `field_metadata`: generate a `REQUIRED_BUILDER_FIELDS: &'static [&'static str]` associated
+///   const on the type, listing the names of the fields that have no default and so must be set
+///   before building.
+///
+/// - `expose_defaults`: generate an associated const on the type for each field whose default is
+///   a literal, named after the field - e.g. `Foo::DEFAULT_X` for a field `x` with
+///   `#[builder(default = 5)]`. The build method uses these consts. Other defaults are not
+///   necessarily const expressions, so they don't get one. Fields whose names only differ in case
+///   (e.g. `foo` and `Foo`) would get the same const, which is an error.
+///
+/// - `phased`: split setting the fields into two phases - the setters of the fields that have a
+///   default can only be called after all the required fields were set. With large structs, this
+///   keeps the required fields together at the start of the builder chain, and makes the setters of
+///   the optional fields a sign that the builder is ready to be built.
+///
+/// - `mergeable`: make the setters of all the fields `setter(mutable)` (see below), which requires
+///   every field with a setter to have a default. Since the builder's type then no longer depends
+///   on which fields are set, the builder gets a `merge(self, other: Self) -> Self` method that
+///   fills the fields that are not set in it with their values from `other` - e.g. for combining
+///   configuration from several sources:
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(Debug, PartialEq, TypedBuilder)]
+///    #[builder(mergeable)]
+///    struct Config {
+///        #[builder(default = 80)]
+///        port: u16,
+///        #[builder(default)]
+///        verbose: bool,
+///    }
+///
+///    let mut from_args = Config::builder();
+///    from_args.verbose(true);
+///    let mut from_file = Config::builder();
+///    from_file.port(8080).verbose(false);
+///    assert_eq!(from_args.merge(from_file).build(), Config { port: 8080, verbose: true });
+///    ```
+///
+/// - `build_into`: generate a `build_into(self, dest: &mut MaybeUninit<Foo>) -> &mut Foo` method
+///   next to the build method, which writes the fields one by one into `dest` instead of creating
+///   the struct and moving it there - e.g. for large `#[repr(C)]` structs that are handed to FFI.
+///   The defaults and validators are evaluated exactly as in the build method. The generated code
+///   uses `unsafe` internally (writing the fields of `#[repr(packed)]` structs without assuming
+///   they are aligned), and is not supported for enums.
+///
+///    ```
+///    use std::mem::MaybeUninit;
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(TypedBuilder)]
+///    #[builder(build_into)]
+///    #[repr(C)]
+///    struct Packet {
+///        id: u32,
+///        #[builder(default = [0; 64])]
+///        payload: [u8; 64],
+///    }
+///
+///    let mut packet = MaybeUninit::uninit();
+///    let packet = Packet::builder().id(1).build_into(&mut packet);
+///    assert_eq!(packet.id, 1);
+///    ```
+///
+/// - `getters`: generate a `get_x(&self) -> &T` method on the builder for each field `x` (that is
+///   not skipped), which is only available once the field is set - e.g. for reading the values set
+///   so far in a generic function that takes the builder. Fields with a mutable setter can be read
+///   at any time, so their getter returns an `Option<&T>` that is `None` until they are set.
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(TypedBuilder)]
+///    #[builder(getters)]
+///    struct Foo {
+///        x: i32,
+///        #[builder(default)]
+///        y: String,
+///    }
+///
+///    let builder = Foo::builder().x(1);
+///    assert_eq!(*builder.get_x(), 1);
+///    // `builder.get_y()` does not compile, since `y` was not set.
+///    ```
+///
+/// - `assert_zero_cost`: check at compile time that the builder takes no more space than the
+///   values of the fields stored in it (i.e. that its type-state tracking is zero-sized). The check
+///   is done for each builder type the build method is called on, so it also covers generic
+///   structs.
+///
+/// - `build_as_tuple`: make the build method return the values of all the fields as a tuple, in
+///   declaration order, instead of the struct - for structs that only group the inputs of the
+///   builder. Can't be combined with `build_method(into)`.
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(TypedBuilder)]
+///    #[builder(build_as_tuple)]
+///    struct Range {
+///        start: u32,
+///        #[builder(default = start + 10)]
+///        end: u32,
+///    }
+///
+///    assert_eq!(Range::builder().start(5).build(), (5, 15));
+///    ```
+///
+/// - `test_builder`: generate a `Foo::test_builder()` method for tests, which returns the same
+///   builder as `Foo::builder()` - but since it requires every field to have a default (and
+///   reports the fields that don't have one otherwise), the builder can be built right away, and
+///   a test only calls the setters of the fields it cares about. Not supported for enums.
+///
+/// - `no_must_use`: don't mark the builder type with `#[must_use]`, for code that intentionally
+///   discards builders (e.g. in macro-generated code).
+///
+/// - `build_result`: make the build method return a `Result`, even if no field has a `validate`
+///   setting. See `setter(validate = ...)` below.
+///
+/// - `cfg(...)`: only generate the builder when the given `cfg` predicate holds, e.g.
+///   `cfg(feature = "builders")`. All the generated items - the builder type, its methods and the
+///   `builder()` method on the struct - are gated behind it.
+///
+/// - `rename_all = "..."`: name the setters of all the fields with a case convention, like serde's
+///   `rename_all` - one of `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`, `"camelCase"`,
+///   `"snake_case"` and `"SCREAMING_SNAKE_CASE"`. The convention applies to the setter's name
+///   including its `prefix` and `suffix`, and a field's own `setter(name = ...)` overrides it.
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(TypedBuilder)]
+///    #[builder(rename_all = "camelCase")]
+///    struct Config {
+///        max_retries: u32,
+///        #[builder(default, setter(prefix = "with_"))]
+///        user_agent: String,
+///    }
+///
+///    let config = Config::builder().maxRetries(3).withUserAgent("curl".to_owned()).build();
+///    assert_eq!(config.max_retries, 3);
+///    ```
+///
+/// - `crate_module = ...`: the path to the `core` crate that the generated code uses, given
+///   either as a path or as a string. Defaults to `::core`, which may not be available in crates
+///   with an unusual prelude setup. This only relocates `core` - the path to this crate is set
+///   separately, with `crate = ...`.
+///
+/// - `crate = ...`: the path to this crate that the generated code uses, given either as a path
+///   or as a string. Defaults to `::typed_builder`, which is not available when the dependency is
+///   renamed or when the derive is used through a re-export.
+///
+/// - `module = "..."`: put the builder type and everything generated for it (except for the
+///   builder method) in a module with that name, next to the struct, instead of in the struct's
+///   own module - e.g. with `module = "foo_builder"` the builder type is
+///   `foo_builder::FooBuilder`. The module has the builder type's visibility, and the generated
+///   items keep the visibilities they would have next to the struct. The module imports everything
+///   from the struct's module, but paths that start with `self::` or `super::` in the struct's
+///   attributes and field types are resolved from the new module. Since a module inside a function
+///   can't see the function's local items, the struct has to be declared outside of functions.
+///
+/// - `mutators(...)` takes functions that can mutate fields inside of the builder. The functions
+///   must take `&mut self` as their first argument, and may take more arguments after it. Inside
+///   them, `self.field` refers to the value that was already set for `field`. The builder gets a
+///   method of the same name (with the same arguments, minus `self`), which can only be called
+///   once all the fields the function accesses are set - the other fields can be in any state.
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(PartialEq, Debug, TypedBuilder)]
+///    #[builder(mutators(
+///        fn add_to_all(&mut self, value: i32) {
+///            self.x += value;
+///            self.y += value;
+///        }
+///    ))]
+///    struct Foo {
+///        x: i32,
+///        y: i32,
+///    }
+///
+///    assert_eq!(Foo::builder().x(1).y(2).add_to_all(10).build(), Foo { x: 11, y: 12 });
+///    ```
+///
+/// - `extra_impl { ... }` takes items (functions, consts) that are put as-is in an `impl` block
+///   of the builder type, which is generic over the state of all the fields. Unlike mutators,
+///   these can't access the values of the fields - since the fields may not have been set - but
+///   they are available on the builder at any point, without naming its generics. The struct's
+///   generic parameters can be used in them.
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(TypedBuilder)]
+///    #[builder(extra_impl {
+///        fn max_len(&self) -> usize {
+///            16
+///        }
+///    })]
+///    struct Foo {
+///        name: String,
+///    }
+///
+///    let builder = Foo::builder();
+///    let name = "a".repeat(builder.max_len());
+///    assert_eq!(builder.name(name).build().name.len(), 16);
+///    ```
+///
+/// - `setter(...)` is a shorthand for `field_defaults(setter(...))`. It is mostly useful for
+///   setting a `prefix` and/or `suffix` for all the setters of the type, e.g.
+///   `#[builder(setter(prefix = "set_"))]`.
+///
 /// On each **field**, the following values are permitted:
 ///
 /// - `default`: make the field optional, defaulting to `Default::default()`. This requires that
 ///   the field type implement `Default`. Mutually exclusive with any other form of default.
 ///
-/// - `default = …`: make the field optional, defaulting to the expression `…`.
+/// - `default = …`: make the field optional, defaulting to the expression `…`. The expression can
+///   refer to other fields by name, and will get the final value of that field - whether it was
+///   set, defaulted, or skipped. The defaults are evaluated in an order that puts each one after
+///   the fields it refers to, so fields can refer to fields declared after them as well. Defaults
+///   that refer to each other in a cycle are an error. For example,
+///   `#[builder(default = x + y, setter(skip))]` computes a field from the fields `x` and `y`.
+///   Every field has a value by the time the defaults that refer to it are evaluated, but the
+///   value is referred to by name just like a local variable - so a default that uses a field of a
+///   non-`Copy` type by value moves it out of the struct, which is an error. Use e.g.
+///   `default = name.clone()` instead.
+///   The expression is evaluated inside the build method, so `Self` refers to the builder type -
+///   use the struct's name to refer to the struct itself, e.g. `default = Foo::default_x()`.
+///   For the same reason, using `self` (which would be the builder) is an error - refer to the
+///   other fields by name instead.
+///   The expression may diverge - with e.g. `default = panic!("`x` must be set")`, the setter can
+///   be left out at compile time but building without it fails at runtime with that message (add
+///   `build_method(attr(track_caller))` to report the caller's location). Such a default is only
+///   evaluated when the field was not set.
+///
+/// - `default_code = "…"`: make the field optional, defaulting to the expression `…`. Mutually
+///   exclusive with any other form of default. Note that
+///   you need to enclose it in quotes, which allows you to use it together with other custom
+///   derive proc-macro crates that complain about "expected literal".
+///   Note that if `...` contains a string, you can use raw string literals to avoid escaping the
+///   double quotes - e.g. `#[builder(default_code = r#""default text".to_owned()"#)]`.
+///
+/// - `skip`: the same as `setter(skip)` (see below).
 ///
-/// - `default_code = "…"`: make the field optional, defaulting to the expression `…`. Note that
-///    you need to enclose it in quotes, which allows you to use it together with other custom
-///    derive proc-macro crates that complain about "expected literal".
-///    Note that if `...` contains a string, you can use raw string literals to avoid escaping the
-///    double quotes - e.g. `#[builder(default_code = r#""default text".to_owned()"#)]`.
+/// - `via_mutators(init = …)`: the field has no setter, and starts with the value `…` instead.
+///   Mutators (see `mutators(...)` on the type) can access it right away, and the build method
+///   uses whatever value it has by then. Mutually exclusive with `default`, `skip` and
+///   `setter(mutable)`.
+///
+/// - `group = "..."`: put the field in a group of mutually exclusive fields, of which at most one
+///   may be set. This is checked when the struct is built, so the build method returns
+///   `Result<Foo, FooBuilderError>` (like with `validate`), and `FooBuilderError` gets a variant
+///   named after the group, holding the names of the first two fields of it that were set. The
+///   fields of a group must have a default, and a group must have at least two fields.
 ///
 /// - `setter(...)`: settings for the field setters. The following values are permitted inside:
 ///
 ///   - `doc = "…"`: sets the documentation for the field's setter on the builder type. This will be
 ///     of no value unless you enable docs for the builder type with `#[builder(doc)]` or similar on
-///     the type.
+///     the type. When not set, the setter uses the doc comments of the field itself. When set, the
+///     field's doc comments are only used for the field and not for the setter.
 ///
 ///   - `skip`: do not define a method on the builder for this field. This requires that a default
-///     be set.
+///     be set, which is evaluated by the build method and can be computed from other fields.
+///     Fields of type `PhantomData<...>` (written with any path) are skipped with a default of
+///     `PhantomData` automatically, unless their own attributes configure the setter with
+///     `setter(...)` - `#[builder(!skip)]` gives them a setter as well.
 ///
 ///   - `into`: automatically convert the argument of the setter method to the type of the field.
 ///     Note that this conversion interferes with Rust's type inference and integer literal
 ///     detection, so this may reduce ergonomics if the field type is generic or an unsigned integer.
+///     Without it the setter takes the field type itself. When enabled for all fields with
+///     `field_defaults(setter(into))`, a single field can opt out with `setter(!into)` (or
+///     `setter(into = false)`), which makes its setter take the field type itself. This is usually
+///     what numeric fields want, since their setters can then be called with plain literals:
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(TypedBuilder)]
+///     #[builder(field_defaults(setter(into)))]
+///     struct Foo {
+///         name: String,
+///         // With `into`, `count(5)` would not compile - the literal would be an `i32`, which
+///         // doesn't convert into a `u64`.
+///         #[builder(setter(into = false))]
+///         count: u64,
+///     }
+///
+///     let foo = Foo::builder().name("a").count(5).build();
+///     assert_eq!(foo.count, 5);
+///     ```
 ///
-///   - `strip_option`: for `Option<...>` fields only, this makes the setter wrap its argument with
+///   - `into = SomeType`: like `into`, but the setter accepts `impl Into<SomeType>` instead, and the
+///     value is converted in two steps - first into `SomeType`, and then into the field's type. For
+///     example, an `Arc<str>` field with `setter(into = String)` can be set with a `&str`, which
+///     would not work with `into` alone. `SomeType` must implement `Into` for the field's type.
+///
+///   - `try_into`: like `into`, but for fallible conversions. The setter accepts any value that
+///     implements `TryInto` for the field's type, and returns a `Result` with the builder, or with
+///     the conversion's error if the value could not be converted. Since the error surfaces when
+///     the setter is called, chaining further setters requires `?` (or otherwise unwrapping the
+///     result): `Foo::builder().x(value)?.y(1).build()`. Mutually exclusive with `into`,
+///     `from_ref`, `into_iter`, `transform` and `strip_bool`, and can be combined with
+///     `strip_option`.
+///
+///   - `strip_option`: for `Option<...>` fields only (written with any path, e.g.
+///     `std::option::Option<...>`, but not through a type alias), this makes the setter wrap its argument with
 ///     `Some(...)`, relieving the caller from having to do this. Note that with this setting on
 ///     one cannot set the field to `None` with the setter - so the only way to get it to be `None`
 ///     is by using `#[builder(default)]` and not calling the field's setter.
 ///
+///   - `auto_box`: for `Box<...>` fields only, this makes the setter accept the boxed type and put
+///     it in a `Box` - which is convenient for recursive types. Combined with `into` (or
+///     `try_into`), the argument is converted to the boxed type before boxing it. Mutually
+///     exclusive with `strip_option`, `strip_bool` and `transform`.
+///
+///   - `auto_arc` and `auto_rc`: the same as `auto_box`, for `Arc<...>` and `Rc<...>` fields
+///     respectively - the setter accepts the pointed-to type and wraps it with `Arc::new` or
+///     `Rc::new`, saving the caller from doing so for shared configuration. `auto_box`, `auto_arc`
+///     and `auto_rc` are mutually exclusive with each other.
+///
+///     When the pointed-to type is a trait object, e.g. a `Box<dyn Fn(i32) -> i32>` callback, the
+///     setter takes `impl Trait` instead - here `impl Fn(i32) -> i32 + 'static`, where the
+///     `'static` is left out if the trait object has a lifetime bound of its own - and coerces the
+///     pointer to the trait object. This can't be combined with `into` or `try_into`.
+///
+///   - `wrap_with = path::to::function`: the setter passes its argument to the function, which
+///     returns the field's value - e.g. `wrap_with = Arc::new` for an `Arc<T>` field, whose setter
+///     then takes a `T`. The setter's argument type is the only generic argument of the field's
+///     type, and for other types (such as newtypes) it is given with `into = ...`, in which case the
+///     setter accepts anything that converts into that type. Combined with `into`, the argument is
+///     converted before being passed to the function. Mutually exclusive with `transform`,
+///     `strip_option`, `strip_bool`, `auto_box`, `try_into`, `from_ref` and `into_iter`.
+///
+///    ```
+///    use std::sync::Arc;
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(Debug, PartialEq)]
+///    struct Name(String);
+///
+///    impl Name {
+///        fn new(name: String) -> Self {
+///            Self(name.to_uppercase())
+///        }
+///    }
+///
+///    #[derive(TypedBuilder)]
+///    struct Foo {
+///        #[builder(setter(wrap_with = Arc::new))]
+///        shared: Arc<Vec<i32>>,
+///        #[builder(setter(wrap_with = Name::new, into = String))]
+///        name: Name,
+///    }
+///
+///    let foo = Foo::builder().shared(vec![1]).name("a").build();
+///    assert_eq!(*foo.shared, [1]);
+///    assert_eq!(foo.name, Name("A".to_owned()));
+///    ```
+///
+///   - `from_ref`: for `Clone` types, this makes the setter take a reference to the value and
+///     clone it, for when the caller only has a reference. This always clones - when the caller
+///     has a value it no longer needs, a regular setter avoids the copy. Can be combined with
+///     `strip_option` (taking a reference to the inner type) and `auto_box`, and is mutually
+///     exclusive with `into`, `try_into`, `into_iter`, `transform` and `strip_bool`.
+///
+///   - `into_iter`: for collection fields (anything that implements both `IntoIterator` and
+///     `FromIterator` for its items, e.g. `Vec<T>`, `HashSet<T>` or `HashMap<K, V>`), this makes
+///     the setter accept any `impl IntoIterator<Item = impl Into<Item>>` and collect it into the
+///     field. Unlike `each`, which adds one item per call, this sets the whole collection at once.
+///     Can be combined with `strip_option` and `each`, and is mutually exclusive with `into`,
+///     `try_into`, `from_ref`, `transform` and `strip_bool`.
+///
+///   - `nested` or `nested = SomeBuilder`: for fields whose type also derives `TypedBuilder`, this
+///     makes the setter take a closure that gets a new builder of the field's type (from its
+///     `builder()` method) and returns the built value, for configuring nested structs inline. The
+///     builder type is the field type's name followed by `Builder` (e.g. `InnerBuilder<T>` for
+///     `Inner<T>`), and can be given with `nested = ...` when it is named differently - with a
+///     turbofish for generic builders, e.g. `nested = InnerBuilder::<T>`. When the field type's
+///     builder method is renamed, use `nested(builder = Inner::make)` - along with
+///     `builder_type = ...` when the builder type is renamed too. Can be combined with
+///     `strip_option` and `auto_box`, and is mutually exclusive with `into`, `try_into`,
+///     `from_ref`, `into_iter`, `transform` and `strip_bool`.
+///
+///    ```
+///    use typed_builder::TypedBuilder;
+///
+///    #[derive(Debug, PartialEq, TypedBuilder)]
+///    struct Server {
+///        #[builder(default = 80)]
+///        port: u16,
+///        host: String,
+///    }
+///
+///    #[derive(TypedBuilder)]
+///    struct Config {
+///        #[builder(setter(nested))]
+///        server: Server,
+///    }
+///
+///    let config = Config::builder()
+///        .server(|server| server.host("localhost".to_owned()).build())
+///        .build();
+///    assert_eq!(config.server, Server { port: 80, host: "localhost".to_owned() });
+///    ```
+///
 ///   - `strip_bool`: for `bool` fields only, this makes the setter receive no arguments and simply
 ///     set the field's value to `true`. When used, the `default` is automatically set to `false`.
 ///
+///   - `mutable`: for fields with a `default` only, this makes the setter take `&mut self` and
+///     return `&mut Self` instead of consuming the builder. Such a setter can be called any number
+///     of times, the last value wins, and setting the field does not change the builder's type - so
+///     the builder can be stored in a variable and set conditionally or inside a loop. Mutators
+///     can't access these fields, and `each` can't be used with them.
+///
 ///   - `transform = |param1: Type1, param2: Type2 ...| expr`: this makes the setter accept
 ///     `param1: Type1, param2: Type2 ...` instead of the field type itself. The parameters are
 ///     transformed into the field type using the expression `expr`. The transformation is performed
-///     when the setter is called.
+///     when the setter is called. Mutually exclusive with `into`, `strip_option` and `strip_bool`.
+///     Together with `name = "..."` this makes setters for fields that are derived from other
+///     values, e.g. `setter(name = "full_name", transform = |first: &str, last: &str|
+///     format!("{first} {last}"))`.
+///
+///   - `validate = |value| -> Result<(), ErrorType> { ... }`: validate the field's value when the
+///     struct is built. The closure receives a reference to the value and must explicitly declare
+///     its return type. When any field has a validator, the build method returns
+///     `Result<Foo, FooBuilderError>` instead of `Foo`, where `FooBuilderError` is a generated enum
+///     with a variant for each validated field (named after the field) holding the validator's
+///     error. The validators run in field order, and the first failure is returned. The error type
+///     is generic over the struct's generic parameters that the validators' error types use. It
+///     implements `Display` (showing the validator's error with `Debug`), and `std::error::Error`
+///     when the `std` feature of this crate is enabled, which it is by default.
+///
+///   - `name = "..."` sets the name of the setter method, instead of using the field's name. The
+///     struct field itself keeps its original name. This overrides `prefix` and `suffix`. A setter
+///     whose name differs from the field's (also through `prefix`, `suffix` or `rename_all`) gets
+///     a `#[doc(alias = "...")]` with the field's name, so searching the docs for it finds the
+///     setter.
+///
+///   - `each = "..."`: for `Vec<...>` fields only, this adds another method with the specified
+///     name, which pushes a single item (accepting anything that converts `Into` the item type)
+///     and can be called repeatedly. The first call starts from the field's `default` (or from an
+///     empty `Vec` if it has none), unless the field was already set with its regular setter.
+///     Because of that, the `default` of such a field cannot refer to other fields.
 ///
 ///   - `prefix = "..."` prepends the setter method with the specified prefix. For example, setting
 ///     `prefix = "with_"` results in setters like `with_x` or `with_y`. This option is combinable
@@ -158,11 +693,94 @@
 ///   - `suffix = "..."` appends the setter method with the specified suffix. For example, setting
 ///     `suffix = "_value"` results in setters like `x_value` or `y_value`. This option is combinable
 ///     with `prefix = "..."`.
+///
+///   - `vis = "..."` sets the visibility of the setter (and of the `each` method), e.g.
+///     `vis = "pub(crate)"`. By default, setters have the same visibility as the builder type.
 pub use typed_builder_macro::TypedBuilder;
 
-// It'd be nice for the compilation tests to live in tests/ with the rest, but short of pulling in
-// some other test runner for that purpose (e.g. compiletest_rs), rustdoc compile_fail in this
-// crate is all we can use.
+/// Used by the generated build method to resolve fields that have a default: `()` means the field
+/// was not set and the default is used, `(T,)` means it was set. Fields with a mutable setter are
+/// stored as an `Option<T>`.
+///
+/// This is an implementation detail, but it can be named in bounds when writing a custom build
+/// method over a builder with unset optional fields. It lives here rather than being generated
+/// for each struct, so crates with many builders only get it once.
+#[doc(hidden)]
+pub trait Optional<T> {
+    fn into_option(self) -> Option<T>;
+}
+
+impl<T> Optional<T> for () {
+    fn into_option(self) -> Option<T> {
+        None
+    }
+}
+
+impl<T> Optional<T> for (T,) {
+    fn into_option(self) -> Option<T> {
+        Some(self.0)
+    }
+}
+
+impl<T> Optional<T> for Option<T> {
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+/// The `std::error::Error` trait, which the error types of build methods that return a `Result`
+/// implement when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use std::error::Error as StdError;
+
+/// Expands to the items given to it only when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_std {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+/// Expands to the items given to it only when the `std` feature is enabled.
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_std {
+    ($($item:item)*) => {};
+}
+
+/// Reports whether a field in a builder's type-state was set, for `#[builder(introspect)]` and for
+/// checking `#[builder(group = "...")]`.
+///
+/// This is an implementation detail and should not be implemented or called directly.
+#[doc(hidden)]
+pub trait IsSet {
+    fn is_set(&self) -> bool;
+}
+
+impl IsSet for () {
+    fn is_set(&self) -> bool {
+        false
+    }
+}
+
+impl<T> IsSet for (T,) {
+    fn is_set(&self) -> bool {
+        true
+    }
+}
+
+impl<T> IsSet for Option<T> {
+    fn is_set(&self) -> bool {
+        self.is_some()
+    }
+}
+
+// The compilation tests here only check that the code fails to compile. The ones that also check
+// the error message and where it points are UI tests in tests/ui, which are run with trybuild.
 
 #[doc(hidden)]
 /// When a property is skipped, you can't set it:
@@ -195,7 +813,7 @@ pub use typed_builder_macro::TypedBuilder;
 /// ```
 ///
 /// `skip` without `default` is disallowed:
-/// (“error: #[builder(skip)] must be accompanied by default”)
+/// (“error: #[builder(setter(skip))] must be accompanied by default or default_code”)
 ///
 /// ```compile_fail
 /// use typed_builder::TypedBuilder;
@@ -207,6 +825,40 @@ pub use typed_builder_macro::TypedBuilder;
 /// }
 /// ```
 ///
+/// Building without setting a required field:
+/// (“argument #1 of type `FooBuilder_Error_Missing_required_field_y` is missing”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let _ = Foo::builder().x(1).build();
+/// ```
+///
+/// Mutators can't be called before the fields they access are set:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(mutators(
+///     fn inc_x(&mut self) {
+///         self.x += 1;
+///     }
+/// ))]
+/// struct Foo {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let _ = Foo::builder().y(1).inc_x().x(2).build();
+/// ```
+///
 /// `clone` does not work if non-Clone fields have already been set
 ///
 /// ```compile_fail
@@ -241,6 +893,541 @@ pub use typed_builder_macro::TypedBuilder;
 /// let _ = Foo::builder().x(Uncloneable).clone();
 /// ```
 ///
+/// `strip_option` on a field that is not an `Option`:
+/// (“error: can't `strip_option` - field is not `Option<...>`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(strip_option))]
+///     x: i32,
+/// }
+/// ```
+///
+/// Setter names must be valid identifiers:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(name = "with x"))]
+///     x: i32,
+/// }
+/// ```
+///
+/// `default` and `default_code` can't be used together:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(default = 1, default_code = "2")]
+///     x: i32,
+/// }
+/// ```
+///
+/// The `skip` shorthand also requires a default:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, TypedBuilder)]
+/// struct Foo {
+///     #[builder(skip)]
+///     y: i8,
+/// }
+/// ```
+///
+/// And it can't be combined with `setter(skip)`:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, TypedBuilder)]
+/// struct Foo {
+///     #[builder(default, skip, setter(skip))]
+///     y: i8,
+/// }
+/// ```
+///
+/// `transform` can't be combined with `into`:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(into, transform = |x: u32| x * 2))]
+///     x: u32,
+/// }
+/// ```
+///
+/// The result of `transform` must be of the field's type:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(transform = |x: u32| x as u64))]
+///     x: u32,
+/// }
+/// ```
+///
+/// Two setters can't have the same name:
+/// (“duplicate setter name `y`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(name = "y"))]
+///     x: i32,
+///     y: i32,
+/// }
+/// ```
+///
+/// This includes `each` setters:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(each = "items"))]
+///     items: Vec<i32>,
+/// }
+/// ```
+///
+/// Mutable setters require a default:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(mutable))]
+///     x: i32,
+/// }
+/// ```
+///
+/// Defaults can't depend on each other in a cycle:
+/// (“the defaults of fields `x` -> `y` -> `x` depend on each other in a cycle”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(default = y + 1)]
+///     x: i32,
+///     #[builder(default = x + 1)]
+///     y: i32,
+/// }
+/// ```
+///
+/// `try_into` can't be combined with `into`:
+/// (“try_into conflicts with into”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(try_into, into))]
+///     x: u8,
+/// }
+/// ```
+///
+/// `from_ref` can't be combined with `into`:
+/// (“from_ref conflicts with into”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(from_ref, into))]
+///     x: String,
+/// }
+/// ```
+///
+/// Setters with a restricted visibility can't be called from outside of it:
+/// (“method `x` is private”)
+///
+/// ```compile_fail
+/// mod foo {
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(TypedBuilder)]
+///     pub struct Foo {
+///         #[builder(setter(vis = ""))]
+///         pub x: i32,
+///     }
+/// }
+///
+/// foo::Foo::builder().x(1).build();
+/// ```
+///
+/// The builder can't be created without `builder()` outside of the struct's module, since its
+/// fields are private:
+/// (“fields `fields` and `phantom` of struct `FooBuilder` are private”)
+///
+/// ```compile_fail
+/// mod foo {
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(TypedBuilder)]
+///     #[builder(doc)]
+///     pub struct Foo {
+///         pub x: i32,
+///     }
+/// }
+///
+/// foo::FooBuilder {
+///     fields: ((1,),),
+///     phantom: core::marker::PhantomData,
+/// }
+/// .build();
+/// ```
+///
+/// Defaults can't move the value of another field:
+/// (“use of moved value: `name`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     name: String,
+///     #[builder(default = name)]
+///     title: String,
+/// }
+/// ```
+///
+/// Fields that are only changed by mutators need an initial value:
+/// (“via_mutators requires an initial value”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(via_mutators())]
+///     x: i32,
+/// }
+/// ```
+///
+/// Settings that take a single value can't be given again in another `#[builder(...)]` attribute:
+/// (“`name` is already set”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(build_method(name = finish))]
+/// #[builder(build_method(name = done))]
+/// struct Foo {
+///     x: i32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(prefix = "with_"))]
+///     #[builder(setter(prefix = "set_"))]
+///     x: i32,
+/// }
+/// ```
+///
+/// With `phased`, optional fields can't be set before the required ones:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(phased)]
+/// struct Foo {
+///     x: i32,
+///     #[builder(default)]
+///     y: i32,
+/// }
+///
+/// Foo::builder().y(2).x(1).build();
+/// ```
+///
+/// Enum variants without named fields are not supported:
+/// (“TypedBuilder only supports enum variants with named fields”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// enum Foo {
+///     Bar { x: i32 },
+///     Baz(i32),
+/// }
+/// ```
+///
+/// All the fields of a `mergeable` builder need a default:
+/// (“`mergeable` requires every field with a setter to have a default”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(mergeable)]
+/// struct Foo {
+///     x: i32,
+/// }
+/// ```
+///
+/// Integer literals are not inferred through `into` setters:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(into))]
+///     count: u64,
+/// }
+///
+/// Foo::builder().count(5).build();
+/// ```
+///
+/// `build_as_tuple` replaces the conversion done by `build_method(into)`:
+/// (“`build_as_tuple` can't be used with `build_method(into)`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(build_as_tuple, build_method(into))]
+/// struct Foo {
+///     x: i32,
+/// }
+/// ```
+///
+/// The bounds of `build_method(bounds(...))` are required for building:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(build_method(bounds(T: Clone)))]
+/// struct Foo<T> {
+///     x: T,
+/// }
+///
+/// struct NotClone;
+///
+/// Foo::builder().x(NotClone).build();
+/// ```
+///
+/// The getters of `#[builder(getters)]` are only available once their field is set:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(getters)]
+/// struct Foo {
+///     #[builder(default)]
+///     x: i32,
+/// }
+///
+/// Foo::builder().get_x();
+/// ```
+///
+/// The argument type of `wrap_with` must be given with `into = ...` when it can't be taken from the
+/// field's type:
+/// (“can't tell the argument type of `wrap_with` - give it with `into = ...`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// struct Name(String);
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(wrap_with = Name))]
+///     name: Name,
+/// }
+/// ```
+///
+/// `wrap_with` replaces the conversions that turn the argument into the field's value:
+/// (“strip_option conflicts with wrap_with”)
+///
+/// ```compile_fail
+/// use std::sync::Arc;
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(wrap_with = Arc::new, strip_option))]
+///     x: Option<Arc<i32>>,
+/// }
+/// ```
+///
+/// Unions are not supported:
+/// (“TypedBuilder is not supported for unions - only for structs and enums”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// union Foo {
+///     x: i32,
+///     y: f32,
+/// }
+/// ```
+///
+/// Setter names must be valid identifiers, which rules out the kebab case conventions:
+/// (“`kebab-case` can't be used for method names”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(rename_all = "kebab-case")]
+/// struct Foo {
+///     max_retries: u32,
+/// }
+/// ```
+///
+/// The builder type of a `nested` setter can only be guessed for named types:
+/// (“can't tell the builder type of the field - give it with `nested = ...`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(nested))]
+///     x: (i32, i32),
+/// }
+/// ```
+///
+/// The fields of a group must be optional:
+/// (“the fields of a group must have a setter and a default”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(default, group = "auth")]
+///     token: Option<String>,
+///     #[builder(group = "auth")]
+///     password: Option<String>,
+/// }
+/// ```
+///
+/// `auto_arc` only works on `Arc` fields:
+/// (“can't `auto_arc` - field is not `Arc<...>`”)
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(auto_arc))]
+///     x: Rc<i32>,
+/// }
+/// ```
+///
+/// `auto_box` of a trait object can't convert the argument with `into`:
+/// (“`auto_box` of a trait object can't be combined with `into` or `try_into`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(auto_box, into))]
+///     callback: Box<dyn Fn()>,
+/// }
+/// ```
+///
+/// `test_builder` requires all the fields to have defaults:
+/// (“`test_builder` requires all the fields to have a default - missing for `x`”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(test_builder)]
+/// struct Foo {
+///     x: i32,
+///     #[builder(default)]
+///     y: i32,
+/// }
+/// ```
+///
+/// The fields can't be moved out of a struct that implements `Drop`, so it can't have
+/// `into_builder`: (“conflicting implementations of trait
+/// `Foo_into_builder_requires_that_it_does_not_implement_Drop`”)
+///
+/// ```compile_fail,E0119
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(into_builder)]
+/// struct Foo {
+///     x: i32,
+/// }
+///
+/// impl Drop for Foo {
+///     fn drop(&mut self) {}
+/// }
+/// ```
+///
+/// `assert_zero_cost` rejects a builder that keeps state at runtime, like the `Option` a mutable
+/// setter's field is stored in: (“the builder is larger than the values of its fields”)
+///
+/// ```compile_fail,E0080
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(assert_zero_cost)]
+/// struct Foo {
+///     x: u32,
+///     #[builder(default, setter(mutable))]
+///     y: u32,
+/// }
+///
+/// let mut builder = Foo::builder().x(1);
+/// builder.y(2);
+/// let _ = builder.build();
+/// ```
+///
+/// With `module = "..."`, the generated items are not in the struct's module:
+/// (“cannot find type `FooBuilder` in this scope”)
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(module = "foo_builder")]
+/// struct Foo {
+///     x: i32,
+/// }
+///
+/// fn main() {
+///     let _: FooBuilder<((i32,),)> = Foo::builder().x(1);
+/// }
+/// ```
+///
 /// Handling deprecated fields:
 ///
 /// ```compile_fail
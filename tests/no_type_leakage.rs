@@ -12,7 +12,7 @@ struct Foo {
     x: i32,
 }
 
-pub struct Bar(Foo);
+pub struct Bar(#[allow(dead_code)] Foo);
 
 impl Bar {
     pub fn builder() -> BarBuilder {
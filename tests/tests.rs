@@ -66,6 +66,44 @@ fn test_generics() {
     assert!(Foo::builder().x(1).y(2).build() == Foo { x: 1, y: 2 });
 }
 
+#[test]
+fn test_unsized_types_behind_indirection() {
+    use std::fmt::Debug;
+
+    #[derive(TypedBuilder)]
+    #[builder(derive(Debug))]
+    struct Foo<'a, T: ?Sized + Debug> {
+        a: Box<dyn Debug>,
+        #[builder(default = Box::new(2))]
+        b: Box<dyn Debug + Send>,
+        c: Box<str>,
+        #[builder(default)]
+        d: Box<str>,
+        #[builder(setter(into))]
+        e: Box<T>,
+        f: &'a T,
+    }
+
+    let builder = Foo::<str>::builder().a(Box::new(1)).c("c".into()).e("e");
+    assert_eq!(
+        format!("{builder:?}"),
+        r#"FooBuilder { fields: ((1,), (), ("c",), (), ("e",), ()) }"#
+    );
+    let foo = builder.f("f").build();
+    assert_eq!(format!("{:?} {:?}", foo.a, foo.b), "1 2");
+    assert_eq!((&*foo.c, &*foo.d, &*foo.e, foo.f), ("c", "", "e", "f"));
+
+    let foo = Foo::<[i32]>::builder()
+        .a(Box::new("a"))
+        .b(Box::new("b"))
+        .c("c".into())
+        .d("d".into())
+        .e(vec![1])
+        .f(&[2])
+        .build();
+    assert_eq!((&*foo.d, &*foo.e, foo.f), ("d", &[1][..], &[2][..]));
+}
+
 #[test]
 fn test_2d_const_generics() {
     #[derive(PartialEq, TypedBuilder)]
@@ -120,6 +158,58 @@ fn test_into() {
     assert!(Foo::builder().x(1_u8).build() == Foo { x: 1 });
 }
 
+#[test]
+fn test_field_defaults_into() {
+    #[derive(PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    struct Foo<T> {
+        x: i32,
+        y: T,
+        #[builder(setter(!into))]
+        z: u64,
+    }
+
+    assert!(Foo::<i64>::builder().x(1_u8).y(2_i32).z(3).build() == Foo { x: 1, y: 2, z: 3 });
+}
+
+#[test]
+fn test_optional_strip_option_fields() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo<T> {
+        #[builder(default, setter(strip_option))]
+        x: Option<T>,
+        #[builder(default = None, setter(strip_option))]
+        y: Option<&'static str>,
+        #[builder(default = x.as_ref().map(|_| 1), setter(strip_option))]
+        z: Option<i32>,
+    }
+
+    assert_eq!(
+        Foo::<u8>::builder().build(),
+        Foo {
+            x: None,
+            y: None,
+            z: None
+        }
+    );
+    assert_eq!(
+        Foo::builder().x(1_u8).y("a").build(),
+        Foo {
+            x: Some(1),
+            y: Some("a"),
+            z: Some(1),
+        }
+    );
+    assert_eq!(
+        Foo::builder().z(2).x(1_u8).build(),
+        Foo {
+            x: Some(1),
+            y: None,
+            z: Some(2),
+        }
+    );
+}
+
 #[test]
 fn test_strip_option_with_into() {
     #[derive(PartialEq, TypedBuilder)]
@@ -142,6 +232,379 @@ fn test_into_with_strip_option() {
     assert!(Foo::builder().x(1_u8).build() == Foo { x: Some(1) });
 }
 
+#[test]
+fn test_into_and_strip_option_on_string() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(default, setter(into)))]
+    struct Foo {
+        #[builder(setter(into, strip_option))]
+        name: Option<String>,
+        #[builder(setter(strip_option))]
+        #[builder(setter(!into))]
+        #[builder(setter(into))]
+        nickname: Option<String>,
+        #[builder(setter(strip_option))]
+        title: Option<String>,
+    }
+
+    assert_eq!(
+        Foo::builder().name("hi").nickname("there").title(String::from("!")).build(),
+        Foo {
+            name: Some("hi".to_owned()),
+            nickname: Some("there".to_owned()),
+            title: Some("!".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn test_several_setter_options_in_one_list() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(default, setter(into, strip_option, suffix = "_str", doc = "Sets the name."))]
+        name: Option<String>,
+        #[builder(setter(into, prefix = "with_", suffix = "_value"))]
+        value: i64,
+    }
+
+    assert_eq!(
+        Foo::builder().name_str("a").with_value_value(1_i32).build(),
+        Foo {
+            name: Some("a".to_owned()),
+            value: 1,
+        }
+    );
+}
+
+#[test]
+fn test_settings_split_across_attributes() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(prefix = "with_")))]
+    #[builder(derive(Debug), builder_method(name = create))]
+    #[builder(derive(PartialEq), field_defaults(default))]
+    struct Foo {
+        #[builder(!default)]
+        #[builder(setter(into))]
+        #[builder(setter(prefix = "set_"))]
+        name: String,
+        value: i64,
+    }
+
+    let builder = Foo::create().set_name("a");
+    assert_eq!(builder, Foo::create().set_name("a"));
+    assert!(format!("{builder:?}").starts_with("FooBuilder"));
+    assert_eq!(
+        builder.with_value(1).build(),
+        Foo {
+            name: "a".to_owned(),
+            value: 1,
+        }
+    );
+}
+
+#[test]
+fn test_into_false_overrides_field_defaults() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    struct Foo {
+        x: String,
+        #[builder(setter(into = false))]
+        y: i64,
+        #[builder(setter(!into), setter(into = true))]
+        z: String,
+    }
+
+    // `y` takes an `i64`, so the literal is inferred as one instead of being ambiguous.
+    assert_eq!(
+        Foo::builder().x("a").y(1 << 40).z("b").build(),
+        Foo {
+            x: "a".to_owned(),
+            y: 1 << 40,
+            z: "b".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_from_ref_setters() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo<T> {
+        #[builder(setter(from_ref))]
+        x: T,
+        #[builder(default, setter(from_ref, strip_option))]
+        y: Option<String>,
+        #[builder(default, setter(from_ref, mutable))]
+        z: Vec<i32>,
+    }
+
+    let x = vec![1];
+    let y = "a".to_owned();
+    let mut builder = Foo::builder().x(&x).y(&y);
+    builder.z(&x);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            x: vec![1],
+            y: Some("a".to_owned()),
+            z: vec![1],
+        }
+    );
+    assert_eq!(x, [1]);
+    assert_eq!(y, "a");
+}
+
+#[test]
+fn test_enum_variant_builders() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    enum Foo<T> {
+        Bar {
+            x: T,
+            #[builder(default = 2)]
+            y: i32,
+        },
+        #[builder(builder_method(name = create_baz_with_validation))]
+        BazQux {
+            #[builder(setter(validate = |name: &String| -> Result<(), &'static str> {
+                if name.is_empty() { Err("empty") } else { Ok(()) }
+            }))]
+            name: String,
+        },
+        Empty {},
+    }
+
+    let bar: FooBarBuilder<u8, ((u8,), ())> = Foo::bar_builder().x(1_u8);
+    assert_eq!(bar.build(), Foo::Bar { x: 1, y: 2 });
+    assert_eq!(
+        Foo::<u8>::create_baz_with_validation().name("a").build().unwrap(),
+        Foo::BazQux { name: "a".to_owned() }
+    );
+    assert!(matches!(
+        Foo::<u8>::create_baz_with_validation().name("").build(),
+        Err(FooBazQuxBuilderError::name("empty"))
+    ));
+    assert_eq!(Foo::<u8>::empty_builder().build(), Foo::Empty {});
+}
+
+#[test]
+fn test_phantom_data_fields() {
+    use core::marker::PhantomData;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    struct Foo<T, U, V> {
+        x: i32,
+        marker: PhantomData<T>,
+        other_marker: ::std::marker::PhantomData<fn() -> U>,
+        #[builder(!skip)]
+        settable_marker: core::marker::PhantomData<V>,
+        // Configuring the setter keeps it, as it was before `PhantomData` fields were skipped.
+        #[builder(default, setter(into))]
+        into_marker: PhantomData<T>,
+        #[builder(default, setter(doc = "Sets the marker."))]
+        documented_marker: PhantomData<U>,
+    }
+
+    assert_eq!(
+        Foo::<u8, u16, u32>::builder().x(1).build(),
+        Foo {
+            x: 1,
+            marker: PhantomData,
+            other_marker: PhantomData,
+            settable_marker: PhantomData,
+            into_marker: PhantomData,
+            documented_marker: PhantomData,
+        }
+    );
+    assert_eq!(
+        Foo::<u8, u16, u32>::builder()
+            .settable_marker(PhantomData)
+            .x(1)
+            .build()
+            .settable_marker,
+        PhantomData
+    );
+    assert_eq!(
+        Foo::<u8, u16, u32>::builder()
+            .x(1)
+            .into_marker(PhantomData)
+            .documented_marker(PhantomData)
+            .build()
+            .into_marker,
+        PhantomData
+    );
+}
+
+#[test]
+fn test_into_iter_setters() {
+    use std::collections::{BTreeMap, HashSet};
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(into_iter))]
+        names: Vec<String>,
+        #[builder(setter(into_iter))]
+        ids: HashSet<u64>,
+        #[builder(default, setter(into_iter, strip_option))]
+        scores: Option<BTreeMap<String, i32>>,
+        #[builder(default, setter(into_iter, each = "tag"))]
+        tags: Vec<String>,
+    }
+
+    let foo = Foo::builder()
+        .names(["a", "b"])
+        .ids([1_u32, 2, 1])
+        .scores([("a".to_owned(), 1)])
+        .tags(vec!["x"])
+        .tag("y")
+        .build();
+    assert_eq!(foo.names, ["a", "b"]);
+    assert_eq!(foo.ids, HashSet::from([1, 2]));
+    assert_eq!(foo.scores, Some(BTreeMap::from([("a".to_owned(), 1)])));
+    assert_eq!(foo.tags, ["x", "y"]);
+    assert_eq!(
+        Foo::builder()
+            .names(std::iter::empty::<String>())
+            .ids(0..3_u8)
+            .build()
+            .ids
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_auto_box() {
+    #[derive(Debug, PartialEq)]
+    enum Expr {
+        Num(i64),
+        Neg(Box<Expr>),
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Add {
+        #[builder(setter(auto_box))]
+        lhs: Box<Expr>,
+        #[builder(setter(auto_box, into))]
+        rhs: std::boxed::Box<Expr>,
+    }
+
+    impl From<i64> for Expr {
+        fn from(value: i64) -> Self {
+            Self::Num(value)
+        }
+    }
+
+    assert_eq!(
+        Add::builder().lhs(Expr::Neg(Box::new(Expr::Num(1)))).rhs(2).build(),
+        Add {
+            lhs: Box::new(Expr::Neg(Box::new(Expr::Num(1)))),
+            rhs: Box::new(Expr::Num(2)),
+        }
+    );
+}
+
+#[test]
+fn test_auto_arc_and_auto_rc() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(auto_arc))]
+        a: Arc<Vec<i32>>,
+        #[builder(setter(auto_arc, into))]
+        b: std::sync::Arc<String>,
+        #[builder(setter(auto_rc))]
+        c: Rc<i32>,
+        #[builder(default = Rc::new("default".to_owned()), setter(auto_rc, into))]
+        d: Rc<String>,
+    }
+
+    assert_eq!(
+        Foo::builder().a(vec![1]).b("b").c(3).build(),
+        Foo {
+            a: Arc::new(vec![1]),
+            b: Arc::new("b".to_owned()),
+            c: Rc::new(3),
+            d: Rc::new("default".to_owned()),
+        }
+    );
+    assert_eq!(*Foo::builder().a(vec![]).b("b").c(3).d("d").build().d, "d");
+}
+
+#[test]
+fn test_auto_box_trait_object() {
+    use std::fmt::Display;
+    use std::sync::Arc;
+
+    #[derive(TypedBuilder)]
+    struct Foo<'a> {
+        #[builder(setter(auto_box))]
+        callback: Box<dyn Fn(i32) -> i32>,
+        #[builder(setter(auto_box))]
+        borrowing: std::boxed::Box<dyn FnMut() -> usize + 'a>,
+        #[builder(default = Arc::new("default"), setter(auto_arc))]
+        shared: Arc<dyn Display + Send + Sync>,
+    }
+
+    let name = String::from("abc");
+    let offset = 10;
+    let mut foo = Foo::builder().callback(move |x| x + offset).borrowing(|| name.len()).build();
+    assert_eq!((foo.callback)(1), 11);
+    assert_eq!((foo.borrowing)(), 3);
+    assert_eq!(foo.shared.to_string(), "default");
+
+    let foo = Foo::builder().callback(|x| x).borrowing(|| 0).shared(1.5).build();
+    assert_eq!(foo.shared.to_string(), "1.5");
+}
+
+#[test]
+fn test_strip_option_qualified_path() {
+    #[derive(PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(strip_option))]
+        x: std::option::Option<i32>,
+        #[builder(default = Some(2), setter(strip_option))]
+        y: core::option::Option<i32>,
+    }
+
+    assert!(Foo::builder().x(1).build() == Foo { x: Some(1), y: Some(2) });
+    assert!(Foo::builder().x(1).y(3).build() == Foo { x: Some(1), y: Some(3) });
+}
+
+#[test]
+fn test_wrapper_types_with_any_path() {
+    macro_rules! declare_foo {
+        ($option:ty) => {
+            #[derive(Debug, PartialEq, TypedBuilder)]
+            struct Foo {
+                #[builder(setter(strip_option))]
+                a: ::std::option::Option<i32>,
+                #[builder(setter(strip_option))]
+                b: $option,
+                #[builder(setter(auto_box))]
+                c: ::std::boxed::Box<i32>,
+                #[builder(default, setter(each = "d_item"))]
+                d: std::vec::Vec<i32>,
+            }
+        };
+    }
+
+    // Types passed through `macro_rules!` are wrapped in an invisible group.
+    declare_foo!(::core::option::Option<i32>);
+
+    assert_eq!(
+        Foo::builder().a(1).b(2).c(3).d_item(4).build(),
+        Foo {
+            a: Some(1),
+            b: Some(2),
+            c: Box::new(3),
+            d: vec![4],
+        }
+    );
+}
+
 #[test]
 fn test_strip_bool() {
     #[derive(PartialEq, TypedBuilder)]
@@ -208,6 +671,66 @@ fn test_default() {
     );
 }
 
+#[test]
+fn test_default_without_expression() {
+    #[derive(PartialEq, TypedBuilder)]
+    struct Foo<T: Default> {
+        #[builder(default)]
+        x: T,
+        #[builder(default)]
+        y: Option<String>,
+        #[builder(default)]
+        z: Vec<T>,
+    }
+
+    assert!(
+        Foo::<i32>::builder().build()
+            == Foo {
+                x: 0,
+                y: None,
+                z: Vec::new()
+            }
+    );
+    assert!(
+        Foo::builder().x(1).z(vec![2]).build()
+            == Foo {
+                x: 1,
+                y: None,
+                z: vec![2]
+            }
+    );
+}
+
+#[test]
+fn test_default_in_generic_struct() {
+    #[derive(PartialEq, TypedBuilder)]
+    struct Foo<T: Default> {
+        #[builder(default)]
+        items: Vec<T>,
+        #[builder(default = items.iter().map(|_| T::default()).collect(), setter(skip))]
+        defaults: Vec<T>,
+        #[builder(default = defaults.len(), setter(skip))]
+        count: usize,
+    }
+
+    assert!(
+        Foo::<i32>::builder().build()
+            == Foo {
+                items: Vec::new(),
+                defaults: Vec::new(),
+                count: 0
+            }
+    );
+    assert!(
+        Foo::builder().items(vec![1, 2]).build()
+            == Foo {
+                items: vec![1, 2],
+                defaults: vec![0, 0],
+                count: 2
+            }
+    );
+}
+
 #[test]
 fn test_field_dependencies_in_build() {
     #[derive(PartialEq, TypedBuilder)]
@@ -279,6 +802,54 @@ fn test_skip() {
     assert!(Foo::builder().y(1_u8).build() == Foo { x: 0, y: 1, z: 2 });
 }
 
+#[test]
+fn test_skip_shorthand() {
+    #[derive(PartialEq, TypedBuilder)]
+    #[builder(field_defaults(skip, default = 7))]
+    struct Foo {
+        #[builder(!skip, !default)]
+        x: i32,
+        y: i32,
+        #[builder(default = x + 1)]
+        z: i32,
+    }
+
+    assert!(Foo::builder().x(1).build() == Foo { x: 1, y: 7, z: 2 });
+}
+
+#[test]
+fn test_skip_computed_from_earlier_fields() {
+    #[derive(PartialEq, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[builder(default = 10)]
+        y: i32,
+        #[builder(default = x + y, setter(skip))]
+        sum: i32,
+        #[builder(default_code = "sum * 2", setter(skip))]
+        double_sum: i32,
+    }
+
+    assert!(
+        Foo::builder().x(1).build()
+            == Foo {
+                x: 1,
+                y: 10,
+                sum: 11,
+                double_sum: 22
+            }
+    );
+    assert!(
+        Foo::builder().y(2).x(1).build()
+            == Foo {
+                x: 1,
+                y: 2,
+                sum: 3,
+                double_sum: 6
+            }
+    );
+}
+
 #[test]
 fn test_docs() {
     #[derive(TypedBuilder)]
@@ -298,11 +869,41 @@ fn test_docs() {
             )]
         #[allow(dead_code)]
         y: i32,
+        /// The setter for `z` does not use this doc comment, since it has its own.
+        #[builder(default, setter(doc = "Set `z`."))]
+        #[allow(dead_code)]
+        z: i32,
     }
 
     let _ = Point::builder();
 }
 
+#[test]
+fn test_structs_with_no_fields_or_one_field() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Empty {}
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Unit;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct One<T> {
+        x: T,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct OneWithDefault<'a> {
+        #[builder(default)]
+        x: &'a str,
+    }
+
+    assert_eq!(Empty::builder().build(), Empty {});
+    assert_eq!(Unit::builder().build(), Unit);
+    assert_eq!(One::builder().x(1).build(), One { x: 1 });
+    assert_eq!(OneWithDefault::builder().build(), OneWithDefault { x: "" });
+    assert_eq!(OneWithDefault::builder().x("a").build(), OneWithDefault { x: "a" });
+}
+
 #[test]
 fn test_builder_name() {
     #[derive(TypedBuilder)]
@@ -364,7 +965,7 @@ fn test_builder_type_stability_with_other_generics() {
 }
 
 #[test]
-#[allow(clippy::items_after_statements)]
+#[allow(clippy::items_after_statements, clippy::type_complexity)]
 fn test_builder_type_with_default_on_generic_type() {
     #[derive(PartialEq, TypedBuilder)]
     struct Types<X, Y = ()> {
@@ -482,15 +1083,64 @@ fn test_field_defaults_default_value() {
 }
 
 #[test]
-fn test_field_defaults_setter_options() {
-    #[derive(PartialEq, TypedBuilder)]
-    #[builder(field_defaults(setter(strip_option)))]
+fn test_field_defaults_type_default() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(default))]
     struct Foo {
-        x: Option<i32>,
-        #[builder(setter(!strip_option))]
-        y: i32,
-    }
-
+        x: i32,
+        y: Vec<String>,
+        #[builder(default = 5)]
+        z: i32,
+        #[builder(default_code = "String::from(\"w\")")]
+        w: String,
+        #[builder(!default)]
+        v: u8,
+        #[builder(setter(skip))]
+        u: Option<i32>,
+    }
+
+    assert_eq!(
+        Foo::builder().v(1).build(),
+        Foo {
+            x: 0,
+            y: Vec::new(),
+            z: 5,
+            w: "w".to_owned(),
+            v: 1,
+            u: None,
+        }
+    );
+    assert_eq!(Foo::builder().x(2).z(3).v(4).build().x, 2);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_fields() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[deprecated = "use y"]
+        #[builder(default)]
+        x: i32,
+        #[deprecated(note = "use y")]
+        #[builder(default, setter(each = "z_item"))]
+        z: Vec<i32>,
+        y: i32,
+    }
+
+    let foo = Foo::builder().x(1).z_item(2).y(3).build();
+    assert_eq!((foo.x, foo.z, foo.y), (1, vec![2], 3));
+}
+
+#[test]
+fn test_field_defaults_setter_options() {
+    #[derive(PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(strip_option)))]
+    struct Foo {
+        x: Option<i32>,
+        #[builder(setter(!strip_option))]
+        y: i32,
+    }
+
     assert!(Foo::builder().x(1).y(2).build() == Foo { x: Some(1), y: 2 });
 }
 
@@ -549,6 +1199,11 @@ fn test_clone_builder_with_generics() {
     assert!(semi_built2.clone().y(5).build() == Foo { x: "four", y: 5 });
     assert!(semi_built2.y(6).build() == Foo { x: "four", y: 6 });
 
+    // The builder is only `Clone` when the values set in it are, but it can still be built without.
+    let semi_built3 = Foo::builder().x(Uncloneable);
+
+    assert!(semi_built3.y(7).build() == Foo { x: Uncloneable, y: 7 });
+
     // Just to make sure it can build with generic bounds
     #[allow(dead_code)]
     #[derive(TypedBuilder)]
@@ -585,6 +1240,55 @@ fn test_builder_on_struct_with_keywords() {
     );
 }
 
+#[test]
+fn test_raw_identifier_fields_with_other_features() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        into_builder,
+        introspect,
+        mutators(
+            fn bump(&mut self) {
+                self.r#type += 1;
+            }
+        )
+    )]
+    struct Foo {
+        #[builder(setter(validate = |value: &u32| -> Result<(), &'static str> {
+            if *value > 100 { Err("too big") } else { Ok(()) }
+        }))]
+        r#type: u32,
+        #[builder(default = r#type * 2)]
+        r#async: u32,
+        #[builder(default, setter(each = "r#loop"))]
+        r#match: Vec<u32>,
+        #[builder(default, setter(mutable))]
+        r#move: bool,
+    }
+
+    let mut builder = Foo::builder();
+    builder.r#move(true);
+    let builder = builder.r#type(1).bump().r#loop(5_u32);
+    assert_eq!(
+        builder.field_states(),
+        [("type", true), ("async", false), ("match", true), ("move", true)]
+    );
+    let foo = builder.build().unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            r#type: 2,
+            r#async: 4,
+            r#match: vec![5],
+            r#move: true,
+        }
+    );
+    assert_eq!(foo.into_builder().bump().build().unwrap().r#type, 3);
+    assert!(matches!(
+        Foo::builder().r#type(101).build(),
+        Err(FooBuilderError::r#type("too big"))
+    ));
+}
+
 #[test]
 fn test_builder_on_struct_with_keywords_prefix_suffix() {
     #[allow(non_camel_case_types)]
@@ -633,6 +1337,71 @@ fn test_field_setter_transform() {
     );
 }
 
+#[test]
+fn test_field_setter_transform_single_param() {
+    #[derive(PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(transform = |x: u32| u64::from(x) * 2))]
+        x: u64,
+    }
+
+    assert!(Foo::builder().x(21).build() == Foo { x: 42 });
+}
+
+#[test]
+#[deny(clippy::all)]
+fn test_no_clippy_lints_on_generated_code() {
+    type Callback<'a, T> = Box<dyn Fn(Vec<(u8, u16, Option<&'a T>)>) -> Result<Vec<(u8, u16)>, String>>;
+
+    #[derive(TypedBuilder)]
+    #[builder(mutators(
+        fn reset(&mut self) {
+            self.sum = 0;
+        }
+    ))]
+    struct Foo<'a, T> {
+        #[builder(setter(transform = |a: i32, b: i32, c: i32, d: i32, e: i32, f: i32, g: i32, h: i32| a + b + c + d + e + f + g + h))]
+        sum: i32,
+        callback: Callback<'a, T>,
+        #[builder(default, setter(strip_option))]
+        items: Option<Vec<(u8, u16, Option<&'a T>)>>,
+    }
+
+    let foo = Foo::<u8>::builder()
+        .sum(1, 2, 3, 4, 5, 6, 7, 8)
+        .callback(Box::new(|_| Ok(vec![])))
+        .items(vec![(1, 2, None)])
+        .build();
+    assert_eq!(foo.sum, 36);
+    assert_eq!((foo.callback)(vec![]), Ok(vec![]));
+    assert_eq!(foo.items.map(|items| items.len()), Some(1));
+    assert_eq!(
+        Foo::<u8>::builder()
+            .sum(1, 2, 3, 4, 5, 6, 7, 8)
+            .reset()
+            .callback(Box::new(|_| Ok(vec![])))
+            .build()
+            .sum,
+        0
+    );
+}
+
+#[test]
+fn test_field_setter_transform_with_name() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(name = "full_name", transform = |first: &str, last: &str| format!("{first} {last}")))]
+        name: String,
+    }
+
+    assert_eq!(
+        Foo::builder().full_name("John", "Doe").build(),
+        Foo {
+            name: "John Doe".to_owned()
+        }
+    );
+}
+
 #[test]
 fn test_build_method() {
     #[derive(PartialEq, TypedBuilder)]
@@ -644,6 +1413,19 @@ fn test_build_method() {
     assert!(Foo::builder().x(1).__build() == Foo { x: 1 });
 }
 
+#[test]
+fn test_build_method_name_as_string() {
+    #[derive(PartialEq, TypedBuilder)]
+    #[builder(build_method(name = "finish"))]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+    }
+
+    assert!(Foo::builder().x(1).finish() == Foo { x: 1, y: 0 });
+}
+
 #[test]
 fn test_builder_method() {
     #[derive(PartialEq, TypedBuilder)]
@@ -655,6 +1437,18 @@ fn test_builder_method() {
     assert!(Foo::__builder().x(1).build() == Foo { x: 1 });
 }
 
+#[test]
+fn test_builder_method_name_as_string() {
+    #[derive(PartialEq, TypedBuilder)]
+    #[builder(builder_method(name = "configure"), builder_type(name = "FooConfigurator"))]
+    struct Foo {
+        x: i32,
+    }
+
+    let builder: FooConfigurator<_> = Foo::configure();
+    assert!(builder.x(1).build() == Foo { x: 1 });
+}
+
 #[test]
 fn test_builder_type() {
     #[derive(PartialEq, TypedBuilder)]
@@ -751,7 +1545,7 @@ fn test_prefix() {
     }
 
     let foo = Foo::builder().with_x(1).with_y(2).build();
-    assert_eq!(foo, Foo { x: 1, y: 2 })
+    assert_eq!(foo, Foo { x: 1, y: 2 });
 }
 
 #[test]
@@ -764,7 +1558,7 @@ fn test_suffix() {
     }
 
     let foo = Foo::builder().x_value(1).y_value(2).build();
-    assert_eq!(foo, Foo { x: 1, y: 2 })
+    assert_eq!(foo, Foo { x: 1, y: 2 });
 }
 
 #[test]
@@ -777,5 +1571,2063 @@ fn test_prefix_and_suffix() {
     }
 
     let foo = Foo::builder().with_x_value(1).with_y_value(2).build();
-    assert_eq!(foo, Foo { x: 1, y: 2 })
+    assert_eq!(foo, Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_type_level_setter_prefix_and_suffix() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(setter(prefix = "set_", suffix = "_value"))]
+    struct Foo {
+        x: i32,
+        #[builder(setter(name = "y"))]
+        y: i32,
+    }
+
+    let foo = Foo::builder().set_x_value(1).y(2).build();
+    assert_eq!(foo, Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_setter_name() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(prefix = "with_")))]
+    struct Foo {
+        #[builder(setter(name = "set_x"))]
+        x: i32,
+        y: i32,
+        #[builder(default, setter(name = "r#type"))]
+        z: i32,
+    }
+
+    let foo = Foo::builder().set_x(1).with_y(2).r#type(3).build();
+    assert_eq!(foo, Foo { x: 1, y: 2, z: 3 });
+}
+
+#[test]
+fn test_mutators() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        /// Swap `x` and `y`.
+        fn swap_xy(&mut self) {
+            core::mem::swap(&mut self.x, &mut self.y);
+        }
+
+        fn push_x<T: Into<i32>>(&mut self, value: T) {
+            self.v.push(self.x + value.into());
+        }
+    ))]
+    struct Foo {
+        x: i32,
+        y: i32,
+        #[builder(default)]
+        v: Vec<i32>,
+    }
+
+    assert_eq!(Foo::builder().x(1).y(2).swap_xy().build(), Foo { x: 2, y: 1, v: vec![] });
+    // Mutators only require the fields they access to be set
+    assert_eq!(
+        Foo::builder().x(1).v(vec![]).push_x(2_u8).push_x(3).y(4).build(),
+        Foo {
+            x: 1,
+            y: 4,
+            v: vec![3, 4]
+        }
+    );
+}
+
+#[test]
+fn test_mutators_calling_methods_on_self() {
+    trait Factor {
+        fn factor(&self) -> i32 {
+            10
+        }
+
+        fn factor_as<T: From<u8>>(&self) -> T {
+            T::from(2)
+        }
+    }
+
+    impl<T> Factor for T {}
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        fn scale_x(&mut self) {
+            self.x *= self.factor() * self.factor_as::<i32>();
+        }
+    ))]
+    struct Foo {
+        x: i32,
+        y: i32,
+    }
+
+    assert_eq!(Foo::builder().x(2).scale_x().y(1).build(), Foo { x: 40, y: 1 });
+}
+
+#[test]
+fn test_extra_impl() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        extra_impl {
+            const DESCRIPTION: &'static str = "a Foo";
+
+            #[allow(clippy::unused_self)]
+            fn type_name(&self) -> &'static str {
+                core::any::type_name::<T>()
+            }
+        },
+        extra_impl {
+            fn description(&self) -> String {
+                format!("{} of {} bytes", Self::DESCRIPTION, core::mem::size_of_val(self))
+            }
+        }
+    )]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        y: i32,
+    }
+
+    let builder = Foo::builder();
+    assert_eq!(builder.description(), "a Foo of 0 bytes");
+    let builder = builder.x(1_u8);
+    assert_eq!(builder.type_name(), "u8");
+    assert_ne!(builder.y(2).description(), "a Foo of 0 bytes");
+}
+
+#[test]
+fn test_mergeable() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        mergeable,
+        mutators(
+            fn bump(&mut self) {
+                self.revision += 1;
+            }
+        )
+    )]
+    struct Foo<T: Default> {
+        #[builder(default)]
+        x: T,
+        #[builder(default = 2, setter(into))]
+        y: i64,
+        #[builder(default, setter(strip_option))]
+        z: Option<&'static str>,
+        #[builder(via_mutators(init = 0))]
+        revision: u32,
+        #[builder(default = y * 2, setter(skip))]
+        w: i64,
+    }
+
+    let mut first = Foo::builder().bump();
+    first.x(1_u8);
+    let mut second = Foo::builder().bump().bump();
+    second.x(2).y(3_i32).z("a");
+    assert_eq!(
+        first.clone().merge(second.clone()).build(),
+        Foo {
+            x: 1,
+            y: 3,
+            z: Some("a"),
+            revision: 1,
+            w: 6,
+        }
+    );
+    assert_eq!(
+        second.merge(first).build(),
+        Foo {
+            x: 2,
+            y: 3,
+            z: Some("a"),
+            revision: 2,
+            w: 6,
+        }
+    );
+    assert_eq!(
+        Foo::<u8>::builder().merge(Foo::builder()).build(),
+        Foo {
+            x: 0,
+            y: 2,
+            z: None,
+            revision: 0,
+            w: 4,
+        }
+    );
+}
+
+#[test]
+fn test_via_mutators() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        fn add(&mut self, item: i32) {
+            self.items.push(item);
+            self.count += 1;
+        }
+        fn scale(&mut self) {
+            for item in &mut self.items {
+                *item *= self.factor;
+            }
+        }
+    ))]
+    struct Foo {
+        #[builder(via_mutators(init = Vec::new()))]
+        items: Vec<i32>,
+        factor: i32,
+        #[builder(via_mutators(init = 0))]
+        count: usize,
+    }
+
+    assert_eq!(
+        Foo::builder().factor(1).build(),
+        Foo {
+            items: vec![],
+            factor: 1,
+            count: 0,
+        }
+    );
+    assert_eq!(
+        Foo::builder().add(1).add(2).factor(3).scale().build(),
+        Foo {
+            items: vec![3, 6],
+            factor: 3,
+            count: 2,
+        }
+    );
+}
+
+#[test]
+fn test_mutators_with_generics() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        fn duplicate(&mut self) {
+            self.items.extend(self.items.clone());
+        },
+        fn add(&mut self, item: T) {
+            self.items.push(item);
+        },
+    ))]
+    struct Foo<'a, T: Clone> {
+        items: Vec<T>,
+        name: &'a str,
+    }
+
+    assert_eq!(
+        Foo::builder().items(vec![1]).add(2).duplicate().name("foo").build(),
+        Foo {
+            items: vec![1, 2, 1, 2],
+            name: "foo"
+        }
+    );
+}
+
+#[test]
+fn test_setter_each() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(each = "item"))]
+        items: Vec<String>,
+        #[builder(default = vec![1], setter(each = "number"))]
+        numbers: Vec<i32>,
+    }
+
+    assert_eq!(
+        Foo::builder().item("a").number(2).item("b").number(3).build(),
+        Foo {
+            items: vec!["a".to_owned(), "b".to_owned()],
+            numbers: vec![1, 2, 3],
+        }
+    );
+    assert_eq!(
+        Foo::builder().items(vec!["a".to_owned()]).item("b").build(),
+        Foo {
+            items: vec!["a".to_owned(), "b".to_owned()],
+            numbers: vec![1],
+        }
+    );
+}
+
+#[test]
+fn test_setter_each_named_like_the_field() {
+    fn extra() -> Vec<i32> {
+        vec![0]
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(name = "set_items", each = "items"))]
+        items: Vec<i32>,
+        #[builder(default = extra(), setter(each = "extra"))]
+        extras: Vec<i32>,
+    }
+
+    assert_eq!(
+        Foo::builder().items(1).extra(2).items(3).build(),
+        Foo {
+            items: vec![1, 3],
+            extras: vec![0, 2],
+        }
+    );
+    assert_eq!(
+        Foo::builder().set_items(vec![1]).items(2).build(),
+        Foo {
+            items: vec![1, 2],
+            extras: vec![0],
+        }
+    );
+}
+
+#[test]
+fn test_validate() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(validate = |x: &i32| -> Result<(), String> {
+            if *x < 0 { Err(format!("{x} is negative")) } else { Ok(()) }
+        }))]
+        x: i32,
+        #[builder(default = 1, setter(validate = |y| -> Result<(), &'static str> {
+            if *y == 0 { Err("zero") } else { Ok(()) }
+        }))]
+        y: i32,
+    }
+
+    assert_eq!(Foo::builder().x(1).build().unwrap(), Foo { x: 1, y: 1 });
+    assert!(matches!(
+        Foo::builder().x(-1).build(),
+        Err(FooBuilderError::x(message)) if message == "-1 is negative"
+    ));
+    assert!(matches!(Foo::builder().x(1).y(0).build(), Err(FooBuilderError::y("zero"))));
+}
+
+#[test]
+fn test_validate_error_generic_over_the_struct() {
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo<'a, T: FromStr, U> {
+        #[builder(setter(validate = |text: &&'a str| -> Result<(), T::Err> { text.parse::<T>().map(|_| ()) }))]
+        text: &'a str,
+        #[builder(default, setter(strip_option), group = "tag")]
+        name: Option<U>,
+        #[builder(default, setter(strip_option), group = "tag")]
+        id: Option<u32>,
+        #[builder(default)]
+        parsed: Option<T>,
+    }
+
+    let foo: Foo<i32, ()> = Foo::builder().text("1").build().unwrap();
+    assert_eq!(foo.text, "1");
+
+    let error = Foo::<i32, ()>::builder().text("a").build().unwrap_err();
+    assert!(matches!(&error, FooBuilderError::text(_)));
+    assert_eq!(
+        error.to_string(),
+        "validation of `text` failed: ParseIntError { kind: InvalidDigit }"
+    );
+    #[cfg(feature = "std")]
+    {
+        let error: &dyn std::error::Error = &error;
+        assert!(error.source().is_none());
+    }
+
+    let error = Foo::<i32, &str>::builder().text("1").name("a").id(1).build().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "`name` and `id` can't both be set, since they are in the `tag` group"
+    );
+}
+
+#[test]
+fn test_function_local_struct_with_all_generated_items() {
+    // Every item the derive generates (impls, the error enum, ...) must be valid in a function body.
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        builder_impl_default,
+        derive(Debug, PartialEq),
+        field_metadata,
+        into_builder,
+        introspect,
+        mutators(
+            fn increment(&mut self) {
+                self.y += 1;
+            }
+        )
+    )]
+    struct Foo {
+        #[builder(setter(validate = |x: &i32| -> Result<(), &'static str> {
+            if *x < 0 { Err("negative") } else { Ok(()) }
+        }))]
+        x: i32,
+        #[builder(via_mutators(init = 0))]
+        y: i32,
+    }
+
+    assert_eq!(Foo::REQUIRED_BUILDER_FIELDS, ["x"]);
+    let builder = FooBuilder::default().x(1).increment();
+    assert_eq!(builder.field_states(), [("x", true), ("y", true)]);
+    let foo = builder.build().unwrap();
+    assert_eq!(foo, Foo { x: 1, y: 1 });
+    assert_eq!(foo.into_builder().increment().build().unwrap(), Foo { x: 1, y: 2 });
+    assert!(matches!(Foo::builder().x(-1).build(), Err(FooBuilderError::x("negative"))));
+}
+
+#[test]
+fn test_builder_conversion_traits() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default = 2)]
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Bar {
+        #[builder(setter(validate = |x: &i32| -> Result<(), &'static str> {
+            if *x < 0 { Err("negative") } else { Ok(()) }
+        }))]
+        x: i32,
+    }
+
+    // The conversion produces the struct itself, even when the build method is generic over the
+    // type it converts it into.
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_method(into))]
+    struct Baz {
+        x: i32,
+    }
+
+    // `build_result` alone can't fail.
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_result)]
+    struct Qux {
+        x: i32,
+    }
+
+    let foo: Foo<&str> = Foo::builder().x("a").into();
+    assert_eq!(foo, Foo { x: "a", y: 2 });
+    assert_eq!(Foo::from(Foo::builder().y(3).x(1)), Foo { x: 1, y: 3 });
+
+    assert_eq!(Bar::try_from(Bar::builder().x(1)).unwrap(), Bar { x: 1 });
+    let bar: Result<Bar, BarBuilderError> = Bar::builder().x(-1).try_into();
+    assert!(matches!(bar, Err(BarBuilderError::x("negative"))));
+
+    assert_eq!(Baz::from(Baz::builder().x(1)), Baz { x: 1 });
+    assert_eq!(Baz::builder().x(1).build::<Option<_>>(), Some(Baz { x: 1 }));
+    assert_eq!(Qux::from(Qux::builder().x(1)), Qux { x: 1 });
+}
+
+#[test]
+fn test_build_result_without_validators() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_result)]
+    struct Foo {
+        x: i32,
+    }
+
+    let result: Result<Foo, FooBuilderError> = Foo::builder().x(1).build();
+    assert_eq!(result.unwrap(), Foo { x: 1 });
+}
+
+#[test]
+fn test_builder_conversion_opt_out() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_method(no_from))]
+    struct Foo {
+        x: i32,
+    }
+
+    // With `build_method(no_from)`, this doesn't conflict with a generated implementation.
+    impl From<FooBuilder<((i32,),)>> for Foo {
+        fn from(builder: FooBuilder<((i32,),)>) -> Self {
+            let foo = builder.build();
+            Self { x: foo.x + 1 }
+        }
+    }
+
+    assert_eq!(Foo::from(Foo::builder().x(1)), Foo { x: 2 });
+}
+
+#[test]
+fn test_builder_impl_default() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_impl_default)]
+    struct Foo<T: Default>
+    where
+        T: Clone,
+    {
+        #[builder(default)]
+        x: T,
+        #[builder(default = 2)]
+        y: i32,
+    }
+
+    assert_eq!(FooBuilder::<i32>::default().build(), Foo { x: 0, y: 2 });
+    assert_eq!(FooBuilder::default().x(1).build(), Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_inline() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(inline)]
+    struct Foo {
+        x: i32,
+        #[builder(default, setter(each = "item"))]
+        items: Vec<i32>,
+    }
+
+    assert_eq!(Foo::builder().x(1).item(2).build(), Foo { x: 1, items: vec![2] });
+}
+
+#[test]
+fn test_const_generics_with_defaults_and_mutators() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_impl_default, mutators(
+        fn fill(&mut self, value: u32) {
+            self.data = [value; N];
+        }
+    ))]
+    struct Foo<const N: usize> {
+        data: [u32; N],
+        #[builder(default = N)]
+        len: usize,
+    }
+
+    assert_eq!(Foo::builder().data([1, 2]).build(), Foo { data: [1, 2], len: 2 });
+    assert_eq!(
+        FooBuilder::<3>::default().data([0; 3]).fill(7).len(1).build(),
+        Foo { data: [7; 3], len: 1 }
+    );
+}
+
+#[test]
+fn test_tuple_struct() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo(i32, #[builder(default = _0 + 1)] i32, #[builder(setter(into))] String);
+
+    assert_eq!(Foo::builder()._0(1)._2("a").build(), Foo(1, 2, "a".to_owned()));
+    assert_eq!(Foo::builder()._2("b")._1(5)._0(1).build(), Foo(1, 5, "b".to_owned()));
+}
+
+mod two_derives_in_one_module {
+    use typed_builder::TypedBuilder;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    pub struct Foo {
+        #[builder(default)]
+        pub x: i32,
+    }
+
+    // Used to be the name of a helper trait generated by the derive.
+    #[allow(dead_code, non_camel_case_types)]
+    pub trait FooBuilder_Optional {}
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    pub struct Bar {
+        #[builder(default)]
+        pub x: i32,
+    }
+}
+
+mod similar_builder_names {
+    #![allow(non_camel_case_types)]
+
+    use typed_builder::TypedBuilder;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_type(name = Config))]
+    pub struct Foo {
+        #[builder(setter(validate = |x: &i32| -> Result<(), ()> { if *x < 0 { Err(()) } else { Ok(()) } }))]
+        pub x: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_type(name = Config_))]
+    pub struct Bar {
+        pub x: i32,
+    }
+
+    // A generic named like the type-state generic of the field `x`.
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_type(name = Config2))]
+    pub struct Baz<__x> {
+        pub x: __x,
+        #[builder(default)]
+        pub x_: i32,
+    }
+}
+
+#[test]
+fn test_similar_builder_names() {
+    use similar_builder_names::{Bar, Baz, Foo};
+
+    assert_eq!(Foo::builder().x(1).build().unwrap(), Foo { x: 1 });
+    assert_eq!(Bar::builder().x(2).build(), Bar { x: 2 });
+    assert_eq!(Baz::builder().x("a").x_(3).build(), Baz { x: "a", x_: 3 });
+}
+
+#[test]
+fn test_two_derives_in_one_module() {
+    use two_derives_in_one_module::{Bar, Foo};
+
+    assert_eq!(Foo::builder().build(), Foo { x: 0 });
+    assert_eq!(Bar::builder().x(1).build(), Bar { x: 1 });
+}
+
+#[test]
+fn test_defaulted_generic_params() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        builder_impl_default,
+        derive(Debug, PartialEq),
+        introspect,
+        into_builder,
+        mutators(
+            fn double(&mut self) {
+                self.x = self.x + self.x;
+            }
+        ),
+        extra_impl {
+            fn len() -> usize {
+                N
+            }
+        }
+    )]
+    struct Foo<T = u32, const N: usize = 3>
+    where
+        T: Copy + core::ops::Add<Output = T>,
+    {
+        x: T,
+        #[builder(default = [0; N], setter(mutable))]
+        y: [i32; N],
+        #[builder(default, setter(each = "item"))]
+        z: Vec<T>,
+        #[builder(setter(validate = |w: &i32| -> Result<(), &'static str> {
+            if *w < 0 { Err("negative") } else { Ok(()) }
+        }))]
+        w: i32,
+    }
+
+    // The builder type keeps the defaults of the struct's generic parameters.
+    let builder: FooBuilder = FooBuilder::default();
+    assert_eq!(builder, Foo::builder());
+    assert_eq!(FooBuilder::<u8>::len(), 3);
+    let foo: Foo = Foo::builder().x(1).double().item(2_u32).w(0).build().unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 2,
+            y: [0; 3],
+            z: vec![2],
+            w: 0,
+        }
+    );
+    assert_eq!(foo.into_builder().double().field_states().len(), 4);
+}
+
+#[test]
+#[deny(warnings, unused_lifetimes)]
+fn test_lifetime_only_generics() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        builder_impl_default,
+        mutators(
+            fn shorten(&mut self) {
+                self.name = &self.name[1..];
+            }
+        )
+    )]
+    struct Foo<'a, 'b>
+    where
+        'b: 'a,
+    {
+        name: &'a str,
+        parts: &'b [&'b str],
+        #[builder(default = parts.first().copied())]
+        first: Option<&'a str>,
+    }
+
+    let parts = ["x", "y"];
+    assert_eq!(
+        Foo::builder().name("abc").parts(&parts).shorten().build(),
+        Foo {
+            name: "bc",
+            parts: &["x", "y"],
+            first: Some("x"),
+        }
+    );
+}
+
+#[test]
+fn test_where_clause_with_associated_type_bounds() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        builder_impl_default,
+        build_method(into),
+        mutators(
+            fn push(&mut self, item: U) {
+                self.items.push(item);
+            }
+        )
+    )]
+    struct Foo<I, U>
+    where
+        I: Iterator<Item = U> + Clone,
+        U: core::fmt::Debug + PartialEq + Default,
+    {
+        source: I,
+        items: Vec<U>,
+        #[builder(default = source.clone().next().unwrap_or_default())]
+        first: U,
+        #[builder(default, setter(strip_option))]
+        last: Option<I::Item>,
+    }
+
+    assert_eq!(
+        Foo::builder().source(1..3).items(vec![]).push(5).build::<Foo<_, _>>(),
+        Foo {
+            source: 1..3,
+            items: vec![5],
+            first: 1,
+            last: None,
+        }
+    );
+    assert_eq!(
+        FooBuilder::default().source(0..0).items(vec![2]).last(4).build::<Foo<_, _>>(),
+        Foo {
+            source: 0..0,
+            items: vec![2],
+            first: 0,
+            last: Some(4),
+        }
+    );
+}
+
+#[test]
+fn test_skipped_field_is_not_part_of_type_state() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[builder(default = x * 2, setter(skip))]
+        y: i32,
+        #[builder(default)]
+        z: i32,
+    }
+
+    // Only `x` and `z` have slots in the builder's type-state - `y` is always satisfied by its default.
+    let builder: FooBuilder<((i32,), ())> = Foo::builder().x(1);
+    assert_eq!(builder.build(), Foo { x: 1, y: 2, z: 0 });
+}
+
+mod core_reexport {
+    pub use core::*;
+}
+
+#[test]
+fn test_crate_module() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        crate_module = crate::core_reexport,
+        builder_impl_default,
+        mutators(
+            fn double(&mut self) {
+                self.x *= 2;
+            }
+        )
+    )]
+    struct Foo {
+        #[builder(setter(into))]
+        x: i32,
+        #[builder(default, setter(strip_option))]
+        y: Option<i32>,
+        #[builder(default, setter(each = "item", validate = |items| -> Result<(), ()> {
+            if items.len() < 3 { Ok(()) } else { Err(()) }
+        }))]
+        items: Vec<i32>,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(crate_module = "::core")]
+    struct Bar {
+        #[builder(default)]
+        x: i32,
+    }
+
+    assert_eq!(
+        FooBuilder::default().x(1i8).double().y(2).item(3).build().unwrap(),
+        Foo {
+            x: 2,
+            y: Some(2),
+            items: vec![3]
+        }
+    );
+
+    assert_eq!(Bar::builder().build(), Bar { x: 0 });
+}
+
+mod typed_builder_reexport {
+    pub use typed_builder::*;
+}
+
+#[test]
+fn test_crate_path() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(crate = crate::typed_builder_reexport, introspect)]
+    struct Foo {
+        x: i32,
+        #[builder(default = x + 1)]
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(crate = "crate::typed_builder_reexport")]
+    struct Bar {
+        #[builder(default, group = "x_or_y")]
+        x: i32,
+        #[builder(default, group = "x_or_y")]
+        y: i32,
+    }
+
+    assert_eq!(Foo::builder().x(1).field_states(), [("x", true), ("y", false)]);
+    assert_eq!(Foo::builder().x(1).build(), Foo { x: 1, y: 2 });
+    assert_eq!(Foo::builder().x(1).y(3).build(), Foo { x: 1, y: 3 });
+    assert_eq!(Bar::builder().x(1).build().unwrap(), Bar { x: 1, y: 0 });
+    assert!(Bar::builder().x(1).y(2).build().is_err());
+}
+
+#[test]
+fn test_into_explicit_type() {
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(into = String))]
+        name: Arc<str>,
+        #[builder(default, setter(into = String, strip_option))]
+        nickname: Option<Box<str>>,
+    }
+
+    assert_eq!(
+        Foo::builder().name("a").build(),
+        Foo {
+            name: Arc::from("a"),
+            nickname: None,
+        }
+    );
+    assert_eq!(
+        Foo::builder().name(String::from("a")).nickname('b').build(),
+        Foo {
+            name: Arc::from("a"),
+            nickname: Some(Box::from("b")),
+        }
+    );
+}
+
+#[test]
+fn test_try_into_setters() {
+    use core::num::TryFromIntError;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(try_into))]
+        x: u8,
+        #[builder(default, setter(try_into, strip_option))]
+        y: Option<u16>,
+        #[builder(default, setter(try_into, mutable))]
+        z: i8,
+    }
+
+    fn build(x: i32, y: i64) -> Result<Foo, TryFromIntError> {
+        Ok(Foo::builder().x(x)?.y(y)?.build())
+    }
+
+    assert_eq!(build(1, 2), Ok(Foo { x: 1, y: Some(2), z: 0 }));
+    assert!(build(256, 2).is_err());
+    assert!(build(1, -1).is_err());
+
+    let mut builder = Foo::builder();
+    assert!(builder.z(200).is_err());
+    builder.z(-3).unwrap();
+    assert_eq!(builder.x(5u64).unwrap().build(), Foo { x: 5, y: None, z: -3 });
+}
+
+#[test]
+fn test_try_into_setters_with_a_generic_named_value() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo<__Value> {
+        #[builder(setter(try_into))]
+        x: u8,
+        value: __Value,
+    }
+
+    assert_eq!(Foo::builder().x(1_i32).unwrap().value("a").build(), Foo { x: 1, value: "a" });
+}
+
+#[test]
+fn test_into_builder() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(into_builder, mutators(
+        fn increment_x(&mut self) {
+            self.x += 1;
+        }
+    ))]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: String,
+        #[builder(default = x * 10, setter(skip))]
+        z: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(into_builder)]
+    struct Bar<T>(T, #[builder(default)] Option<T>);
+
+    let foo = Foo::builder().x(1).y("a".to_owned()).build();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 1,
+            y: "a".to_owned(),
+            z: 10
+        }
+    );
+    assert_eq!(
+        foo.into_builder().increment_x().build(),
+        Foo {
+            x: 2,
+            y: "a".to_owned(),
+            z: 20
+        }
+    );
+
+    assert_eq!(Bar::builder()._0(1).build().into_builder().build(), Bar(1, None));
+}
+
+#[test]
+fn test_cfg_builder() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(cfg(test), build_result, setter(into))]
+    struct Foo {
+        x: i32,
+    }
+
+    // Without the builder, the struct can have its own `builder()` method.
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(cfg(not(test)))]
+    struct Bar {
+        x: i32,
+    }
+
+    impl Bar {
+        fn builder() -> Self {
+            Self { x: 2 }
+        }
+    }
+
+    assert_eq!(Foo::builder().x(1_i8).build().unwrap(), Foo { x: 1 });
+    assert_eq!(Bar::builder(), Bar { x: 2 });
+}
+
+#[test]
+fn test_cfg_fields() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[cfg(not(test))]
+        y: i32,
+        #[cfg(test)]
+        #[builder(default = x + 1)]
+        z: i32,
+        #[cfg_attr(test, builder(default))]
+        w: i32,
+    }
+
+    assert_eq!(Foo::builder().x(1).build(), Foo { x: 1, z: 2, w: 0 });
+    assert_eq!(Foo::builder().x(1).z(5).w(3).build(), Foo { x: 1, z: 5, w: 3 });
+}
+
+#[test]
+fn test_mutable_setters() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(into_builder)]
+    struct Foo {
+        x: i32,
+        #[builder(default = x + 1, setter(mutable))]
+        y: i32,
+        #[builder(default, setter(mutable, strip_option, into))]
+        z: Option<String>,
+        #[builder(setter(mutable, strip_bool))]
+        w: bool,
+    }
+
+    let mut builder = Foo::builder().x(1);
+    assert_eq!(
+        builder.clone().build(),
+        Foo {
+            x: 1,
+            y: 2,
+            z: None,
+            w: false
+        }
+    );
+    for i in 0..3 {
+        if i % 2 == 0 {
+            builder.y(i);
+        }
+    }
+    builder.z("a").w();
+    let foo = builder.build();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 1,
+            y: 2,
+            z: Some("a".to_owned()),
+            w: true
+        }
+    );
+
+    let mut builder = foo.into_builder();
+    builder.y(5);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            x: 1,
+            y: 5,
+            z: Some("a".to_owned()),
+            w: true
+        }
+    );
+}
+
+#[test]
+fn test_introspect() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(introspect)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        r#y: i32,
+        #[builder(default, setter(mutable))]
+        z: i32,
+        #[builder(default, setter(skip))]
+        w: i32,
+    }
+
+    let mut builder = Foo::<u8>::builder();
+    assert_eq!(builder.field_states(), [("x", false), ("y", false), ("z", false)]);
+    builder.z(1);
+    let builder = builder.y(2);
+    assert_eq!(builder.field_states(), [("x", false), ("y", true), ("z", true)]);
+    assert_eq!(builder.x(3).field_states(), [("x", true), ("y", true), ("z", true)]);
+}
+
+#[test]
+fn test_field_metadata() {
+    #[allow(dead_code)]
+    #[derive(TypedBuilder)]
+    #[builder(field_metadata)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        y: i32,
+        r#type: i32,
+        #[builder(default, setter(skip))]
+        z: i32,
+        #[builder(via_mutators(init = 0))]
+        w: i32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(TypedBuilder)]
+    #[builder(field_metadata)]
+    struct Bar {
+        #[builder(default)]
+        x: i32,
+    }
+
+    assert_eq!(Foo::<u8>::REQUIRED_BUILDER_FIELDS, ["x", "type"]);
+    assert!(Bar::REQUIRED_BUILDER_FIELDS.is_empty());
+}
+
+#[test]
+fn test_expose_defaults() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(expose_defaults)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default = -5)]
+        y: i32,
+        #[builder(default = "a")]
+        r#type: &'static str,
+        #[builder(default = 1.5, setter(skip))]
+        z: f64,
+        #[builder(default = y * 2)]
+        w: i32,
+    }
+
+    assert_eq!(Foo::<u8>::DEFAULT_Y, -5);
+    assert_eq!(Foo::<u8>::DEFAULT_TYPE, "a");
+    assert!((Foo::<u8>::DEFAULT_Z - 1.5).abs() < f64::EPSILON);
+    assert_eq!(
+        Foo::builder().x(1_u8).build(),
+        Foo {
+            x: 1,
+            y: -5,
+            r#type: "a",
+            z: 1.5,
+            w: -10,
+        }
+    );
+}
+
+#[test]
+fn test_phased() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(phased)]
+    struct Foo {
+        x: i32,
+        #[builder(default, setter(strip_option))]
+        y: Option<i32>,
+        z: i32,
+        #[builder(default, setter(each = "item"))]
+        v: Vec<i32>,
+        #[builder(default, setter(mutable))]
+        w: i32,
+    }
+
+    let mut builder = Foo::builder().z(3).x(1).y(2).item(4);
+    builder.w(5);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            x: 1,
+            y: Some(2),
+            z: 3,
+            v: vec![4],
+            w: 5,
+        }
+    );
+}
+
+#[test]
+#[deny(unused_must_use)]
+fn test_no_must_use() {
+    #[derive(TypedBuilder)]
+    #[builder(no_must_use)]
+    struct Foo {
+        #[builder(default)]
+        x: i32,
+    }
+
+    Foo::builder();
+    Foo::builder().x(1);
+    assert_eq!(Foo::builder().x(2).build().x, 2);
+}
+
+mod setter_visibility {
+    use typed_builder::TypedBuilder;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    pub struct Foo {
+        #[builder(setter(vis = "pub(super)"))]
+        pub x: i32,
+        #[builder(default, setter(vis = ""))]
+        pub y: i32,
+    }
+
+    pub fn foo_with_y(y: i32) -> FooBuilder<((), (i32,))> {
+        Foo::builder().y(y)
+    }
+}
+
+#[test]
+fn test_setter_visibility() {
+    use setter_visibility::{foo_with_y, Foo};
+
+    assert_eq!(Foo::builder().x(1).build(), Foo { x: 1, y: 0 });
+    assert_eq!(foo_with_y(2).x(1).build(), Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_attributes_on_generated_declarations() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        builder_method(attr(must_use)),
+        builder_type(attr(derive(Debug, PartialEq), allow(clippy::type_complexity))),
+        build_method(attr(must_use = "the built value is the point"))
+    )]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: Option<i32>,
+    }
+
+    let builder = Foo::builder().x(1);
+    assert!(format!("{builder:?}").starts_with("FooBuilder { fields: ((1,), ()),"));
+    assert_eq!(builder, Foo::builder().x(1));
+    assert_eq!(builder.build(), Foo { x: 1, y: None });
+}
+
+#[test]
+fn test_track_caller_build_method() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_method(attr(track_caller)))]
+    struct Foo {
+        x: i32,
+        #[builder(default = x.checked_add(1).expect("x is too large"))]
+        y: i32,
+        #[builder(default = std::panic::Location::caller().line())]
+        line: u32,
+    }
+
+    let line = line!() + 1;
+    let foo = Foo::builder().x(1).build();
+    assert_eq!(foo, Foo { x: 1, y: 2, line });
+}
+
+#[test]
+fn test_builder_derive() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(derive(Clone, Debug))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default)]
+        y: i32,
+    }
+
+    let builder = Foo::builder().x("a");
+    assert_eq!(format!("{builder:?}"), r#"FooBuilder { fields: (("a",), ()) }"#);
+    assert_eq!(builder.clone().y(1).build(), Foo { x: "a", y: 1 });
+    assert_eq!(builder.build(), Foo { x: "a", y: 0 });
+}
+
+#[test]
+fn test_builder_derive_comparisons() {
+    use std::collections::HashSet;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(derive(PartialEq, Eq, Hash))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default, setter(mutable))]
+        y: i32,
+    }
+
+    assert!(Foo::builder().x("a") == Foo::builder().x("a"));
+    assert!(Foo::builder().x("a") != Foo::builder().x("b"));
+
+    let mut builder = Foo::builder();
+    builder.y(1);
+    assert!(builder != Foo::builder());
+    assert!(builder.x(1) == Foo::builder().x(1).y(1).clone());
+
+    let builders: HashSet<_> = [Foo::builder().x(1), Foo::builder().x(2), Foo::builder().x(1)]
+        .into_iter()
+        .collect();
+    assert_eq!(builders.len(), 2);
+}
+
+#[test]
+fn test_default_referring_to_later_fields() {
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: usize,
+        y: usize,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(default = len * 2)]
+        double_len: usize,
+        #[builder(default = items.len(), setter(skip))]
+        len: usize,
+        #[builder(default = Point { x: len, y: core::cmp::max(len, 1) })]
+        point: Point,
+        items: Vec<i32>,
+    }
+
+    assert_eq!(
+        Foo::builder().items(vec![1, 2]).build(),
+        Foo {
+            double_len: 4,
+            len: 2,
+            point: Point { x: 2, y: 2 },
+            items: vec![1, 2],
+        }
+    );
+    assert_eq!(
+        Foo::builder().items(vec![]).double_len(1).build(),
+        Foo {
+            double_len: 1,
+            len: 0,
+            point: Point { x: 0, y: 1 },
+            items: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_default_bindings_are_not_field_references() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(default = v.len() + w + z)]
+        x: usize,
+        #[builder(default = (1..=2).map(|x| x * 2).collect())]
+        v: Vec<usize>,
+        #[builder(default = { let x = 3; x * 2 })]
+        w: usize,
+        #[builder(default = { fn x() -> usize { 5 } if let Some(x) = w.checked_sub(1) { x } else { x() } })]
+        z: usize,
+    }
+
+    assert_eq!(
+        Foo::builder().build(),
+        Foo {
+            x: 13,
+            v: vec![2, 4],
+            w: 6,
+            z: 5,
+        }
+    );
+    assert_eq!(
+        Foo::builder().w(0).build(),
+        Foo {
+            x: 7,
+            v: vec![2, 4],
+            w: 0,
+            z: 5,
+        }
+    );
+}
+
+#[test]
+fn test_drop_order_with_reordered_defaults() {
+    use std::cell::RefCell;
+
+    struct Noisy<'a> {
+        name: &'static str,
+        log: &'a RefCell<Vec<&'static str>>,
+    }
+
+    impl Drop for Noisy<'_> {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.name);
+        }
+    }
+
+    #[derive(TypedBuilder)]
+    struct Foo<'a> {
+        #[builder(default = Noisy { name: "a", log: c.log })]
+        a: Noisy<'a>,
+        #[builder(default = Noisy { name: "b", log: c.log })]
+        b: Noisy<'a>,
+        c: Noisy<'a>,
+    }
+
+    let log = RefCell::new(Vec::new());
+    let foo = Foo::builder().c(Noisy { name: "c", log: &log }).build();
+    assert_eq!([foo.a.name, foo.b.name, foo.c.name], ["a", "b", "c"]);
+    assert!(log.borrow().is_empty());
+    drop(foo);
+    assert_eq!(*log.borrow(), ["a", "b", "c"]);
+}
+
+#[test]
+fn test_default_referring_to_the_struct_type() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(default = Foo::default_x())]
+        x: i32,
+        #[builder(default = Some(Box::new(Foo::builder().x(x + 1).child(None).build())))]
+        child: Option<Box<Foo>>,
+    }
+
+    impl Foo {
+        fn default_x() -> i32 {
+            1
+        }
+    }
+
+    assert_eq!(
+        Foo::builder().build(),
+        Foo {
+            x: 1,
+            child: Some(Box::new(Foo { x: 2, child: None })),
+        }
+    );
+}
+
+#[test]
+fn test_assert_zero_cost() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(assert_zero_cost)]
+    struct Foo<T> {
+        x: T,
+        #[builder(default, setter(strip_option))]
+        y: Option<i32>,
+    }
+
+    assert_eq!(Foo::builder().x(1_u8).build(), Foo { x: 1, y: None });
+    assert_eq!(Foo::builder().x("a").y(2).build(), Foo { x: "a", y: Some(2) });
+    assert_eq!(
+        core::mem::size_of_val(&Foo::builder().x(1_u64).y(2)),
+        core::mem::size_of::<((u64,), (Option<i32>,))>()
+    );
+}
+
+#[test]
+fn test_build_as_tuple() {
+    #[allow(dead_code)]
+    #[derive(TypedBuilder)]
+    #[builder(build_as_tuple)]
+    struct Foo<T: AsRef<[u8]>> {
+        x: T,
+        #[builder(default = x.as_ref().len(), setter(skip))]
+        len: usize,
+        #[builder(default, setter(strip_option))]
+        y: Option<i32>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(TypedBuilder)]
+    #[builder(build_as_tuple, build_result)]
+    struct Single {
+        x: i32,
+    }
+
+    assert_eq!(Foo::builder().x("ab").build(), ("ab", 2, None));
+    assert_eq!(Foo::builder().y(1).x(vec![0_u8]).build(), (vec![0], 1, Some(1)));
+
+    assert_eq!(Single::builder().x(1).build().unwrap(), (1,));
+}
+
+#[test]
+fn test_non_exhaustive() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(into_builder)]
+    #[non_exhaustive]
+    struct Foo {
+        x: i32,
+        #[builder(default = x + 1)]
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    enum Bar {
+        #[non_exhaustive]
+        Baz { x: i32 },
+    }
+
+    let foo = Foo::builder().x(1).build();
+    assert_eq!(foo, Foo { x: 1, y: 2 });
+    assert_eq!(foo.into_builder().build(), Foo { x: 1, y: 2 });
+    assert_eq!(Bar::baz_builder().x(1).build(), Bar::Baz { x: 1 });
+}
+
+#[test]
+fn test_build_method_bounds() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_method(bounds(T: Clone, T: Default)))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default = x.clone())]
+        y: T,
+    }
+
+    struct NotClone;
+
+    // The setters don't require the bounds of the build method.
+    let _ = Foo::builder().x(NotClone);
+
+    let foo: Foo<i32> = Foo::builder().x(1).build();
+    assert_eq!(foo, Foo { x: 1, y: 1 });
+    assert_eq!(Foo::from(Foo::builder().x("a").y("b")), Foo { x: "a", y: "b" });
+}
+
+#[test]
+fn test_getters() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(
+        getters,
+        mutators(
+            fn bump(&mut self) {
+                self.count += 1;
+            }
+        )
+    )]
+    struct Foo<T> {
+        r#type: T,
+        #[builder(default = 2)]
+        y: i32,
+        #[builder(default, setter(mutable))]
+        z: Option<i32>,
+        #[builder(via_mutators(init = 0))]
+        count: u32,
+    }
+
+    let mut builder = Foo::builder().bump();
+    assert_eq!(builder.get_z(), None);
+    builder.z(Some(3));
+    assert_eq!(builder.get_z(), Some(&Some(3)));
+    assert_eq!(*builder.get_count(), 1);
+
+    let builder = builder.r#type("a").y(4);
+    assert_eq!(*builder.get_type(), "a");
+    assert_eq!(*builder.get_y(), 4);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            r#type: "a",
+            y: 4,
+            z: Some(3),
+            count: 1,
+        }
+    );
+}
+
+#[test]
+fn test_wrap_with() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(Debug, PartialEq)]
+    struct Meters(f64);
+
+    impl Meters {
+        fn from_km(km: f64) -> Self {
+            Self(km * 1000.0)
+        }
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(wrap_with = Arc::new))]
+        x: Arc<i32>,
+        #[builder(setter(wrap_with = Rc::new, into))]
+        y: Rc<String>,
+        #[builder(default = Meters(0.0), setter(wrap_with = Meters::from_km, into = f64))]
+        z: Meters,
+    }
+
+    assert_eq!(
+        Foo::builder().x(1).y("a").z(2_f32).build(),
+        Foo {
+            x: Arc::new(1),
+            y: Rc::new("a".to_owned()),
+            z: Meters(2000.0),
+        }
+    );
+}
+
+#[test]
+fn test_build_into() {
+    use core::mem::MaybeUninit;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_into)]
+    #[repr(C)]
+    struct Foo {
+        x: Vec<i32>,
+        #[builder(default = x.len())]
+        len: usize,
+        #[builder(default, setter(strip_option))]
+        dest: Option<i32>,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_into)]
+    struct Bar(
+        #[builder(setter(validate = |x: &i32| -> Result<(), &'static str> { if *x < 0 { Err("negative") } else { Ok(()) } }))]
+        i32,
+    );
+
+    let mut foo = MaybeUninit::uninit();
+    let foo = Foo::builder().x(vec![1, 2]).build_into(&mut foo);
+    assert_eq!(
+        *foo,
+        Foo {
+            x: vec![1, 2],
+            len: 2,
+            dest: None,
+        }
+    );
+    foo.len = 3;
+    assert_eq!(foo.len, 3);
+
+    let mut bar = MaybeUninit::uninit();
+    assert_eq!(*Bar::builder()._0(1).build_into(&mut bar).unwrap(), Bar(1));
+    assert!(matches!(
+        Bar::builder()._0(-1).build_into(&mut bar),
+        Err(BarBuilderError::_0("negative"))
+    ));
+}
+
+#[test]
+fn test_build_into_packed() {
+    use core::mem::MaybeUninit;
+
+    // The fields after `a` are misaligned, so they must be written without assuming alignment.
+    #[derive(TypedBuilder)]
+    #[builder(build_into)]
+    #[repr(C, packed)]
+    struct Packed {
+        a: u8,
+        b: u32,
+        #[builder(default = 3)]
+        c: u64,
+    }
+
+    // Aligning the destination makes sure `b` is not aligned by chance.
+    #[repr(C, align(8))]
+    struct Aligned(MaybeUninit<Packed>);
+
+    let mut dest = Aligned(MaybeUninit::uninit());
+    let packed = Packed::builder().a(1).b(2).build_into(&mut dest.0);
+    assert_ne!(core::ptr::addr_of!(packed.b) as usize % core::mem::align_of::<u32>(), 0);
+    let Packed { a, b, c } = *packed;
+    assert_eq!((a, b, c), (1, 2, 3));
+}
+
+#[test]
+fn test_into_cow_with_lifetime() {
+    use std::borrow::Cow;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(into)))]
+    struct Foo<'a> {
+        name: Cow<'a, str>,
+        #[builder(default, setter(strip_option))]
+        alias: Option<Cow<'a, str>>,
+        #[builder(default, setter(!into, into_iter))]
+        tags: Vec<Cow<'a, str>>,
+    }
+
+    // The borrowed values only need to live as long as the built struct.
+    fn describe(name: &str) -> Foo<'_> {
+        Foo::builder().name(name).tags([name, "tag"]).build()
+    }
+
+    let name = String::from("a");
+    assert_eq!(
+        describe(&name),
+        Foo {
+            name: Cow::Borrowed("a"),
+            alias: None,
+            tags: vec![Cow::Borrowed("a"), Cow::Borrowed("tag")],
+        }
+    );
+    assert_eq!(
+        Foo::builder().name(String::from("b")).alias("c").build(),
+        Foo {
+            name: Cow::Owned("b".to_owned()),
+            alias: Some(Cow::Borrowed("c")),
+            tags: Vec::new(),
+        }
+    );
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_rename_all() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(rename_all = "camelCase")]
+    struct Foo {
+        max_retry_count: u32,
+        #[builder(setter(name = "timeout"))]
+        timeout_ms: u32,
+        #[builder(default, setter(prefix = "with_", each = "tag"))]
+        tags: Vec<String>,
+        #[builder(default)]
+        r#type: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct Bar {
+        x_y: i32,
+    }
+
+    assert_eq!(
+        Foo::builder().maxRetryCount(3).timeout(10).tag("a").r#type(1).build(),
+        Foo {
+            max_retry_count: 3,
+            timeout_ms: 10,
+            tags: vec!["a".to_owned()],
+            r#type: 1,
+        }
+    );
+    assert_eq!(
+        Foo::builder().maxRetryCount(3).timeout(10).withTags(vec![]).build().tags,
+        Vec::<String>::new()
+    );
+    assert_eq!(Bar::builder().X_Y(1).build(), Bar { x_y: 1 });
+}
+
+#[test]
+fn test_nested_setters() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Inner<T> {
+        x: T,
+        #[builder(default)]
+        y: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_type(name = Custom))]
+    struct Renamed {
+        z: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Outer {
+        #[builder(setter(nested))]
+        inner: Inner<u8>,
+        #[builder(default, setter(nested, strip_option))]
+        optional: Option<Inner<&'static str>>,
+        #[builder(setter(nested = Custom, auto_box))]
+        renamed: Box<Renamed>,
+    }
+
+    assert_eq!(
+        Outer::builder()
+            .inner(|inner| inner.x(1).build())
+            .renamed(|renamed| renamed.z(2).build())
+            .optional(|inner| inner.y(3).x("a").build())
+            .build(),
+        Outer {
+            inner: Inner { x: 1, y: 0 },
+            optional: Some(Inner { x: "a", y: 3 }),
+            renamed: Box::new(Renamed { z: 2 }),
+        }
+    );
+}
+
+#[test]
+fn test_nested_setters_with_a_renamed_builder_method() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_method(name = make))]
+    struct Inner {
+        x: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_method(name = make), builder_type(name = Custom))]
+    struct Renamed {
+        z: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Outer {
+        #[builder(setter(nested(builder = Inner::make)))]
+        inner: Inner,
+        #[builder(default, setter(nested(builder_type = Custom, builder = Renamed::make), strip_option))]
+        renamed: Option<Renamed>,
+    }
+
+    assert_eq!(
+        Outer::builder()
+            .inner(|inner| inner.x(1).build())
+            .renamed(|renamed| renamed.z(2).build())
+            .build(),
+        Outer {
+            inner: Inner { x: 1 },
+            renamed: Some(Renamed { z: 2 }),
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, TypedBuilder)]
+#[builder(build_method(attr(track_caller)))]
+struct DivergingDefaults {
+    #[builder(default = panic!("`x` must be set"), setter(into))]
+    x: u32,
+    #[builder(default = x + 1)]
+    y: u32,
+}
+
+#[test]
+fn test_diverging_default_is_only_evaluated_when_unset() {
+    assert_eq!(DivergingDefaults::builder().x(1_u8).build(), DivergingDefaults { x: 1, y: 2 });
+}
+
+#[test]
+#[should_panic(expected = "`x` must be set")]
+fn test_diverging_default_panics_when_unset() {
+    let _ = DivergingDefaults::builder().y(1).build();
+}
+
+#[test]
+fn test_diverging_default_reports_the_caller() {
+    thread_local! {
+        static PANIC_LINE: core::cell::Cell<Option<u32>> = const { core::cell::Cell::new(None) };
+    }
+
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|info| {
+        PANIC_LINE.with(|line| line.set(info.location().map(std::panic::Location::line)));
+    }));
+    let line = line!() + 1;
+    let result = std::panic::catch_unwind(|| DivergingDefaults::builder().y(1).build());
+    std::panic::set_hook(previous_hook);
+
+    assert!(result.is_err());
+    assert_eq!(PANIC_LINE.with(core::cell::Cell::get), Some(line));
+}
+
+#[test]
+fn test_group() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(default, setter(strip_option), group = "auth")]
+        token: Option<String>,
+        #[builder(default, setter(strip_option), group = "auth")]
+        password: Option<String>,
+        #[builder(default, setter(strip_option), group = "auth")]
+        certificate: Option<String>,
+        #[builder(default = 30)]
+        timeout: u32,
+    }
+
+    assert_eq!(
+        Foo::builder().build().unwrap(),
+        Foo {
+            token: None,
+            password: None,
+            certificate: None,
+            timeout: 30,
+        }
+    );
+    assert_eq!(
+        Foo::builder().password("hunter2".to_owned()).build().unwrap(),
+        Foo {
+            token: None,
+            password: Some("hunter2".to_owned()),
+            certificate: None,
+            timeout: 30,
+        }
+    );
+    assert!(matches!(
+        Foo::builder()
+            .certificate("cert".to_owned())
+            .token("token".to_owned())
+            .build(),
+        Err(FooBuilderError::auth("token", "certificate"))
+    ));
+    assert!(matches!(
+        Foo::try_from(
+            Foo::builder()
+                .token("token".to_owned())
+                .password("hunter2".to_owned())
+                .certificate("cert".to_owned())
+        ),
+        Err(FooBuilderError::auth("token", "password"))
+    ));
+}
+
+#[test]
+fn test_group_with_a_raw_name() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    struct Foo {
+        #[builder(default, setter(strip_option), group = "r#type")]
+        x: Option<i32>,
+        #[builder(default, setter(strip_option), group = "r#type")]
+        y: Option<i32>,
+    }
+
+    assert_eq!(Foo::builder().x(1).build().unwrap(), Foo { x: Some(1), y: None });
+    let error = Foo::builder().x(1).y(2).build().unwrap_err();
+    assert!(matches!(error, FooBuilderError::r#type("x", "y")));
+    assert_eq!(
+        error.to_string(),
+        "`x` and `y` can't both be set, since they are in the `type` group"
+    );
+}
+
+#[test]
+fn test_doc_examples() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(doc_examples, builder_method(name = make), build_method(name = finish))]
+    struct Foo<T> {
+        x: T,
+        #[builder(default, setter(into))]
+        r#type: String,
+        #[builder(default, setter(skip))]
+        z: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(doc_examples)]
+    enum Shape {
+        Circle { radius: f64 },
+    }
+
+    assert_eq!(
+        Foo::make().x(1).r#type("a").finish(),
+        Foo {
+            x: 1,
+            r#type: "a".to_owned(),
+            z: 0
+        }
+    );
+    assert_eq!(Shape::circle_builder().radius(1.0).build(), Shape::Circle { radius: 1.0 });
+}
+
+#[test]
+fn test_test_builder() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(test_builder, mutators(
+        fn double_retries(&mut self) {
+            self.retries *= 2;
+        }
+    ))]
+    struct Config {
+        #[builder(default = "localhost".to_owned(), setter(into))]
+        host: String,
+        #[builder(default = 8080)]
+        port: u16,
+        #[builder(via_mutators(init = 1))]
+        retries: u32,
+    }
+
+    assert_eq!(
+        Config::test_builder().build(),
+        Config {
+            host: "localhost".to_owned(),
+            port: 8080,
+            retries: 1,
+        }
+    );
+    assert_eq!(
+        Config::test_builder().port(1234).double_retries().build(),
+        Config {
+            host: "localhost".to_owned(),
+            port: 1234,
+            retries: 2,
+        }
+    );
+}
+
+// A module inside of a function can't see the function's items, so the structs are declared
+// outside of the test.
+mod builder_modules {
+    use typed_builder::TypedBuilder;
+
+    fn default_port() -> u16 {
+        8080
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(module = "config_builder", mutators(
+        fn double_port(&mut self) {
+            self.port *= 2;
+        }
+    ))]
+    pub(super) struct Config {
+        #[builder(setter(into))]
+        pub(super) host: String,
+        #[builder(via_mutators(init = default_port()))]
+        pub(super) port: u16,
+        #[builder(default, setter(vis = "", strip_option))]
+        pub(super) timeout: Option<u32>,
+    }
+
+    impl Config {
+        pub(super) fn with_timeout(builder: config_builder::ConfigBuilder<((String,), (u16,), ())>) -> Self {
+            builder.timeout(30).build()
+        }
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(module = "shape_builder")]
+    pub(super) enum Shape {
+        Circle { radius: f64 },
+        Square { side: f64 },
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(module = "r#type")]
+    pub(super) struct Type {
+        pub(super) name: &'static str,
+    }
+}
+
+#[test]
+fn test_module() {
+    use builder_modules::{config_builder::ConfigBuilder, Config, Shape};
+
+    let builder: ConfigBuilder<((String,), (u16,), ())> = Config::builder().host("localhost").double_port();
+    assert_eq!(
+        Config::with_timeout(builder),
+        Config {
+            host: "localhost".to_owned(),
+            port: 16160,
+            timeout: Some(30),
+        }
+    );
+    assert_eq!(Shape::circle_builder().radius(1.0).build(), Shape::Circle { radius: 1.0 });
+    assert_eq!(Shape::square_builder().side(2.0).build(), Shape::Square { side: 2.0 });
+
+    let builder: builder_modules::r#type::TypeBuilder<((&str,),)> = builder_modules::Type::builder().name("u8");
+    assert_eq!(builder.build(), builder_modules::Type { name: "u8" });
+}
+
+#[test]
+#[allow(non_snake_case)]
+fn test_generated_names_dont_collide() {
+    // Would be the name of the error type of `Config`'s builder.
+    #[derive(Debug, PartialEq)]
+    struct ConfigBuilderError;
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(build_result, build_method(error = ConfigError))]
+    struct Config {
+        x: i32,
+    }
+
+    // The hidden types that report repeated and missing fields are named after the builder and the
+    // field, which is `Ab_Error_Repeated_field_x_Error_Repeated_field_y` for both of these.
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_type(name = Ab))]
+    struct A {
+        x_Error_Repeated_field_y: i32,
+    }
+
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(builder_type(name = Ab_Error_Repeated_field_x))]
+    struct B {
+        y: i32,
+    }
+
+    let result: Result<Config, ConfigError> = Config::builder().x(1).build();
+    assert_eq!(result.unwrap(), Config { x: 1 });
+    assert_eq!(ConfigBuilderError, ConfigBuilderError);
+    assert_eq!(
+        A::builder().x_Error_Repeated_field_y(1).build(),
+        A {
+            x_Error_Repeated_field_y: 1
+        }
+    );
+    assert_eq!(B::builder().y(2).build(), B { y: 2 });
+}
+
+#[test]
+fn test_mutators_with_fields_named_like_generated_code() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        fn bump(&mut self) {
+            self.__phantom += 1;
+        }
+    ))]
+    struct Foo {
+        __phantom: i32,
+        __mutator: i32,
+    }
+
+    assert_eq!(
+        Foo::builder().__phantom(1).__mutator(5).bump().build(),
+        Foo {
+            __phantom: 2,
+            __mutator: 5,
+        }
+    );
 }
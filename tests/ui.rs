@@ -0,0 +1,7 @@
+// The errors of the derive - their messages and where they point - are checked against the
+// `.stderr` files next to the sources in tests/ui. After changing an error on purpose, regenerate
+// them with `TRYBUILD=overwrite cargo test --test ui`.
+#[test]
+fn ui() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,10 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(builder_method(name = make))]
+enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(expose_defaults)]
+#[allow(non_snake_case)]
+struct Foo {
+    #[builder(default = 1)]
+    foo: i32,
+    #[builder(default = 2)]
+    Foo: i32,
+}
+
+fn main() {}
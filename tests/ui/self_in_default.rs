@@ -0,0 +1,10 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    x: i32,
+    #[builder(default = self.x + 1)]
+    y: i32,
+}
+
+fn main() {}
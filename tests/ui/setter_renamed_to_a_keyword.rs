@@ -0,0 +1,15 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(rename_all = "camelCase")]
+struct Foo {
+    crate_: i32,
+}
+
+#[derive(TypedBuilder)]
+#[builder(rename_all = "PascalCase")]
+struct Bar {
+    self_: i32,
+}
+
+fn main() {}
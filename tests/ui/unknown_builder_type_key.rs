@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(builder_type(vsi = "pub"))]
+struct Foo {
+    x: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+struct Foo {
+    #[builder(default, setter(strip_option, stirp_bool))]
+    x: Option<bool>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(build_method(into), mutable)]
+struct Foo {
+    x: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use typed_builder::TypedBuilder;
+
+#[derive(TypedBuilder)]
+#[builder(setter(into, prefix = "with_", sufix = "_value"))]
+struct Foo {
+    x: i32,
+}
+
+fn main() {}
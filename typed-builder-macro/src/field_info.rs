@@ -3,32 +3,110 @@ use quote::{quote, ToTokens};
 use syn::{parse::Error, spanned::Spanned};
 
 use crate::util::{
-    apply_subsections, expr_to_lit_string, expr_to_single_string, ident_to_type, path_to_single_string, strip_raw_ident_prefix,
+    apply_subsections, collect_variable_idents, ensure_not_set, expr_to_lit_string, expr_to_single_string,
+    extract_single_type_argument, extract_wrapped_type, ident_to_type, path_to_single_string, strip_raw_ident_prefix,
+    unknown_key_error, RenameRule,
 };
 
+/// Keys accepted directly in a field's `#[builder(...)]`, listed when an unknown one is given.
+const FIELD_KEYS: &[&str] = &[
+    "default",
+    "default = ...",
+    "default_code = \"...\"",
+    "skip",
+    "group = \"...\"",
+    "setter(...)",
+    "via_mutators(init = ...)",
+];
+const FIELD_NEGATABLE_KEYS: &[&str] = &["!default", "!skip"];
+
+/// Keys accepted inside `setter(...)` and `field_defaults(...)`.
+const SETTER_KEYS: &[&str] = &[
+    "doc = ...",
+    "transform = ...",
+    "wrap_with = ...",
+    "validate = ...",
+    "into",
+    "into = ...",
+    "name = ...",
+    "each = ...",
+    "prefix = ...",
+    "suffix = ...",
+    "vis = ...",
+    "skip",
+    "strip_option",
+    "strip_bool",
+    "try_into",
+    "auto_box",
+    "auto_arc",
+    "auto_rc",
+    "from_ref",
+    "into_iter",
+    "nested",
+    "nested = ...",
+    "nested(...)",
+    "mutable",
+];
+/// Keys accepted inside `setter(nested(...))`.
+const NESTED_KEYS: &[&str] = &["builder_type = ...", "builder = ..."];
+const SETTER_NEGATABLE_KEYS: &[&str] = &[
+    "!doc",
+    "!skip",
+    "!into",
+    "!strip_option",
+    "!strip_bool",
+    "!try_into",
+    "!auto_box",
+    "!auto_arc",
+    "!auto_rc",
+    "!from_ref",
+    "!into_iter",
+    "!nested",
+    "!mutable",
+];
+
 #[derive(Debug)]
 pub struct FieldInfo<'a> {
     pub ordinal: usize,
-    pub name: &'a syn::Ident,
+    pub name: syn::Ident,
+    pub member: syn::Member,
     pub generic_ident: syn::Ident,
     pub ty: &'a syn::Type,
+    pub doc_attrs: Vec<&'a syn::Attribute>,
     pub builder_attr: FieldBuilderAttr<'a>,
 }
 
 impl<'a> FieldInfo<'a> {
     pub fn new(ordinal: usize, field: &'a syn::Field, field_defaults: FieldBuilderAttr<'a>) -> Result<FieldInfo<'a>, Error> {
-        if let Some(ref name) = field.ident {
-            FieldInfo {
-                ordinal,
-                name,
-                generic_ident: syn::Ident::new(&format!("__{}", strip_raw_ident_prefix(name.to_string())), Span::call_site()),
-                ty: &field.ty,
-                builder_attr: field_defaults.with(&field.attrs)?,
-            }
-            .post_process()
+        // Tuple struct fields get synthesized `_0`, `_1`, ... names for their setters and bindings,
+        // while `member` keeps track of how to refer to the field when constructing the struct.
+        let (name, member) = if let Some(ref name) = field.ident {
+            (name.clone(), syn::Member::Named(name.clone()))
         } else {
-            Err(Error::new(field.span(), "Nameless field in struct"))
+            (
+                syn::Ident::new(&format!("_{}", ordinal), Span::call_site()),
+                syn::Member::Unnamed(syn::Index {
+                    index: ordinal as u32,
+                    span: Span::call_site(),
+                }),
+            )
+        };
+        let mut builder_attr = field_defaults;
+        // There is nothing to set in a `PhantomData`, so such fields are skipped unless their own
+        // attributes say otherwise - with `#[builder(!skip)]`, or by configuring the setter.
+        if extract_wrapped_type(&field.ty, "PhantomData").is_some() && !configures_setter(&field.attrs)? {
+            builder_attr.skip_with_type_default(field.ty.span());
         }
+        FieldInfo {
+            ordinal,
+            generic_ident: syn::Ident::new(&format!("__{}", strip_raw_ident_prefix(name.to_string())), Span::call_site()),
+            name,
+            member,
+            ty: &field.ty,
+            doc_attrs: field.attrs.iter().filter(|attr| attr.path().is_ident("doc")).collect(),
+            builder_attr: builder_attr.with(&field.attrs)?,
+        }
+        .post_process()
     }
 
     pub fn generic_ty_param(&self) -> syn::GenericParam {
@@ -51,42 +129,73 @@ impl<'a> FieldInfo<'a> {
     }
 
     pub fn type_from_inside_option(&self) -> Option<&syn::Type> {
-        let path = if let syn::Type::Path(type_path) = self.ty {
-            if type_path.qself.is_some() {
-                return None;
-            }
-            &type_path.path
-        } else {
-            return None;
-        };
-        let segment = path.segments.last()?;
-        if segment.ident != "Option" {
-            return None;
+        extract_wrapped_type(self.ty, "Option")
+    }
+
+    pub fn type_from_inside_vec(&self) -> Option<&syn::Type> {
+        extract_wrapped_type(self.ty, "Vec")
+    }
+
+    /// The smart pointer the setter puts its argument in, from `auto_box`, `auto_arc` or `auto_rc` -
+    /// as the key, the name of the pointer type and where the key was given.
+    pub fn auto_pointer(&self) -> Option<(&'static str, &'static str, Span)> {
+        let setter = &self.builder_attr.setter;
+        [
+            ("auto_box", "Box", setter.auto_box),
+            ("auto_arc", "Arc", setter.auto_arc),
+            ("auto_rc", "Rc", setter.auto_rc),
+        ]
+        .into_iter()
+        .find_map(|(key, pointer, span)| Some((key, pointer, span?)))
+    }
+
+    /// The argument type of a `wrap_with` setter - the type named by `into = ...`, or else the
+    /// single generic argument of the field's type (e.g. `T` for an `Arc<T>` field).
+    pub fn type_for_wrap_with(&self) -> Option<&syn::Type> {
+        self.builder_attr
+            .setter
+            .into_type
+            .as_ref()
+            .or_else(|| extract_single_type_argument(self.ty))
+    }
+
+    pub fn setter_method_name(&self) -> Ident {
+        if let Some(name) = &self.builder_attr.setter.name {
+            return name.clone();
         }
-        let generic_params = if let syn::PathArguments::AngleBracketed(generic_params) = &segment.arguments {
-            generic_params
-        } else {
-            return None;
+        let Some(name) = self.renamed_setter_name() else {
+            return self.name.clone();
         };
-        if let syn::GenericArgument::Type(ty) = generic_params.args.first()? {
-            Some(ty)
+        // Renaming can turn the name of a raw identifier field (e.g. `r#type`) back into a keyword.
+        if syn::parse_str::<Ident>(&name).is_ok() {
+            Ident::new(&name, Span::call_site())
         } else {
-            None
+            Ident::new_raw(&name, Span::call_site())
         }
     }
 
-    pub fn setter_method_name(&self) -> Ident {
+    /// The name of the setter after applying `prefix`, `suffix` and `rename_all`, or `None` when it
+    /// is not renamed with them.
+    pub fn renamed_setter_name(&self) -> Option<String> {
         let name = strip_raw_ident_prefix(self.name.to_string());
 
-        if let (Some(prefix), Some(suffix)) = (&self.builder_attr.setter.prefix, &self.builder_attr.setter.suffix) {
-            Ident::new(&format!("{}{}{}", prefix, name, suffix), Span::call_site())
+        let name = if let (Some(prefix), Some(suffix)) = (&self.builder_attr.setter.prefix, &self.builder_attr.setter.suffix) {
+            format!("{}{}{}", prefix, name, suffix)
         } else if let Some(prefix) = &self.builder_attr.setter.prefix {
-            Ident::new(&format!("{}{}", prefix, name), Span::call_site())
+            format!("{}{}", prefix, name)
         } else if let Some(suffix) = &self.builder_attr.setter.suffix {
-            Ident::new(&format!("{}{}", name, suffix), Span::call_site())
+            format!("{}{}", name, suffix)
+        } else if self.builder_attr.setter.rename_all.is_none() {
+            return None;
         } else {
-            self.name.clone()
-        }
+            name
+        };
+        // The case convention applies to the whole name, so that e.g. `with_` and `user_name` become
+        // `withUserName` in camelCase.
+        Some(match self.builder_attr.setter.rename_all {
+            Some(rule) => rule.apply_to_field(&name),
+            None => name,
+        })
     }
 
     fn post_process(mut self) -> Result<Self, Error> {
@@ -107,14 +216,65 @@ impl<'a> FieldInfo<'a> {
                 }),
             }));
         }
+        if let Some(init) = &self.builder_attr.via_mutators {
+            let conflicting = [
+                ("default", self.builder_attr.default.as_ref().map(Spanned::span)),
+                ("skip", self.builder_attr.setter.skip),
+                ("mutable", self.builder_attr.setter.mutable),
+            ];
+            if let Some((caption, span)) = conflicting
+                .iter()
+                .find_map(|(caption, span)| span.map(|span| (caption, span)))
+            {
+                let mut error = Error::new_spanned(init, format_args!("via_mutators conflicts with {}", caption));
+                error.combine(Error::new(span, format_args!("{} set here", caption)));
+                return Err(error);
+            }
+        }
+        if let Some(mutable) = self.builder_attr.setter.mutable {
+            if self.builder_attr.default.is_none() {
+                return Err(Error::new(
+                    mutable,
+                    "#[builder(setter(mutable))] must be accompanied by default or default_code, since the field may never be set",
+                ));
+            }
+            if let Some(each) = &self.builder_attr.setter.each {
+                let mut error = Error::new(mutable, "mutable conflicts with each");
+                error.combine(Error::new_spanned(each, "each set here"));
+                return Err(error);
+            }
+        }
         Ok(self)
     }
+
+    pub fn is_mutable(&self) -> bool {
+        self.builder_attr.setter.mutable.is_some()
+    }
+
+    /// Whether the field has a setter. Fields that are only changed by mutators are stored in the
+    /// builder, but don't have one.
+    pub fn has_setter(&self) -> bool {
+        self.builder_attr.setter.skip.is_none() && self.builder_attr.via_mutators.is_none()
+    }
+
+    /// Whether the field must be set with its setter before the struct can be built.
+    pub fn is_required(&self) -> bool {
+        self.has_setter() && self.builder_attr.default.is_none()
+    }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct FieldBuilderAttr<'a> {
     pub default: Option<syn::Expr>,
+    /// Where `default` was set in the current attribute list, for detecting conflicts.
+    default_set_at: Option<Span>,
+    /// Whether `default` is the type's `Default::default()`, which refers to the `core` crate.
+    type_default: bool,
     pub deprecated: Option<&'a syn::Attribute>,
+    /// Set by `via_mutators(init = ...)` - the initial value of a field that is only changed by mutators.
+    pub via_mutators: Option<syn::Expr>,
+    /// Set by `group = "..."` - at most one field of each group can be set.
+    pub group: Option<syn::Ident>,
     pub setter: SetterSettings,
 }
 
@@ -123,15 +283,43 @@ pub struct SetterSettings {
     pub doc: Option<syn::Expr>,
     pub skip: Option<Span>,
     pub auto_into: Option<Span>,
+    /// Set by `into = ...` - the type the argument is converted to before converting it to the field's type.
+    pub into_type: Option<syn::Type>,
     pub strip_option: Option<Span>,
     pub strip_bool: Option<Span>,
+    pub auto_box: Option<Span>,
+    pub auto_arc: Option<Span>,
+    pub auto_rc: Option<Span>,
+    pub try_into: Option<Span>,
+    pub from_ref: Option<Span>,
+    pub into_iter: Option<Span>,
+    pub nested: Option<Span>,
+    /// Set by `nested = ...` - the builder type of the field's type, when it can't be guessed.
+    pub nested_builder: Option<syn::Type>,
+    /// Set by `nested(builder = ...)` - the function that creates the builder, instead of the field
+    /// type's `builder()` method.
+    pub nested_builder_method: Option<syn::ExprPath>,
+    pub mutable: Option<Span>,
     pub transform: Option<Transform>,
+    /// Set by `wrap_with = ...` - the function that turns the argument into the field's value.
+    pub wrap_with: Option<syn::Expr>,
+    pub validate: Option<Validator>,
+    pub name: Option<Ident>,
+    pub each: Option<Ident>,
     pub prefix: Option<String>,
     pub suffix: Option<String>,
+    /// Set for all the fields by the type's `#[builder(rename_all = "...")]`.
+    pub rename_all: Option<RenameRule>,
+    pub vis: Option<syn::Visibility>,
+    /// The flags that were set in the current attribute list, for detecting repetitions.
+    flags_set_here: Vec<&'static str>,
 }
 
 impl<'a> FieldBuilderAttr<'a> {
     pub fn with(mut self, attrs: &'a [syn::Attribute]) -> Result<Self, Error> {
+        // The field's own attributes may override a default or setter flags set by `field_defaults`.
+        self.default_set_at = None;
+        self.setter.flags_set_here.clear();
         for attr in attrs {
             let list = match &attr.meta {
                 syn::Meta::List(list) => {
@@ -174,7 +362,22 @@ impl<'a> FieldBuilderAttr<'a> {
                     expr_to_single_string(&assign.left).ok_or_else(|| Error::new_spanned(&assign.left, "Expected identifier"))?;
                 match name.as_str() {
                     "default" => {
-                        self.default = Some(*assign.right);
+                        self.set_default(assign.left.span(), *assign.right)?;
+                        Ok(())
+                    }
+                    "group" => {
+                        ensure_not_set(&self.group, &assign.left)?;
+                        let group = expr_to_lit_string(&assign.right)?;
+                        self.group = Some(
+                            syn::parse_str::<Ident>(&group)
+                                .map(|mut ident| {
+                                    ident.set_span(assign.right.span());
+                                    ident
+                                })
+                                .map_err(|_| {
+                                    Error::new_spanned(&assign.right, format!("{:?} is not a valid identifier", group))
+                                })?,
+                        );
                         Ok(())
                     }
                     "default_code" => {
@@ -184,25 +387,38 @@ impl<'a> FieldBuilderAttr<'a> {
                         }) = *assign.right
                         {
                             use std::str::FromStr;
-                            let tokenized_code = TokenStream::from_str(&code.value())?;
-                            self.default =
-                                Some(syn::parse2(tokenized_code).map_err(|e| Error::new_spanned(code, format!("{}", e)))?);
+                            let tokenized_code =
+                                TokenStream::from_str(&code.value()).map_err(|e| Error::new_spanned(&code, format!("{}", e)))?;
+                            let default = syn::parse2(tokenized_code).map_err(|e| Error::new_spanned(&code, format!("{}", e)))?;
+                            self.set_default(assign.left.span(), default)?;
                         } else {
                             return Err(Error::new_spanned(assign.right, "Expected string"));
                         }
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(&assign, format!("Unknown parameter {:?}", name))),
+                    _ => Err(unknown_key_error(&assign.left, FIELD_KEYS)),
                 }
             }
             syn::Expr::Path(path) => {
                 let name = path_to_single_string(&path.path).ok_or_else(|| Error::new_spanned(&path, "Expected identifier"))?;
                 match name.as_str() {
                     "default" => {
-                        self.default = Some(syn::parse2(quote!(::core::default::Default::default())).unwrap());
+                        self.set_default(path.span(), syn::parse2(quote!(::core::default::Default::default())).unwrap())?;
+                        self.type_default = true;
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(&path, format!("Unknown parameter {:?}", name))),
+                    "skip" => {
+                        if self.setter.flags_set_here.contains(&"skip") {
+                            return Err(Error::new(
+                                path.span(),
+                                "Illegal setting - field is already skipped (`skip` is the same as `setter(skip)`)",
+                            ));
+                        }
+                        self.setter.flags_set_here.push("skip");
+                        self.setter.skip = Some(path.span());
+                        Ok(())
+                    }
+                    _ => Err(unknown_key_error(&path, FIELD_KEYS)),
                 }
             }
             syn::Expr::Call(call) => {
@@ -223,10 +439,23 @@ impl<'a> FieldBuilderAttr<'a> {
                         }
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(
-                        &call.func,
-                        format!("Illegal builder setting group name {}", subsetting_name),
-                    )),
+                    "via_mutators" => {
+                        ensure_not_set(&self.via_mutators, &call.func)?;
+                        let mut init = None;
+                        for arg in &call.args {
+                            match arg {
+                                syn::Expr::Assign(assign) if expr_to_single_string(&assign.left).as_deref() == Some("init") => {
+                                    init = Some((*assign.right).clone());
+                                }
+                                _ => return Err(Error::new_spanned(arg, "Expected `init = ...`")),
+                            }
+                        }
+                        self.via_mutators = Some(init.ok_or_else(|| {
+                            Error::new_spanned(&call, "via_mutators requires an initial value - `via_mutators(init = ...)`")
+                        })?);
+                        Ok(())
+                    }
+                    _ => Err(unknown_key_error(&call.func, FIELD_KEYS)),
                 }
             }
             syn::Expr::Unary(syn::ExprUnary {
@@ -240,9 +469,15 @@ impl<'a> FieldBuilderAttr<'a> {
                     match name.as_str() {
                         "default" => {
                             self.default = None;
+                            self.default_set_at = None;
+                            self.type_default = false;
+                            Ok(())
+                        }
+                        "skip" => {
+                            self.setter.skip = None;
                             Ok(())
                         }
-                        _ => Err(Error::new_spanned(path, "Unknown setting".to_owned())),
+                        _ => Err(unknown_key_error(&path, FIELD_NEGATABLE_KEYS)),
                     }
                 } else {
                     Err(Error::new_spanned(expr, "Expected simple identifier".to_owned()))
@@ -252,18 +487,97 @@ impl<'a> FieldBuilderAttr<'a> {
         }
     }
 
+    fn set_default(&mut self, span: Span, default: syn::Expr) -> Result<(), Error> {
+        if let Some(previous_span) = self.default_set_at {
+            let mut error = Error::new(
+                span,
+                "default is already set - `default` and `default_code` are mutually exclusive",
+            );
+            error.combine(Error::new(previous_span, "default set here"));
+            return Err(error);
+        }
+        // The default is evaluated inside the build method, where `self` is the builder.
+        let mut idents = Vec::new();
+        collect_variable_idents(&default, &mut idents);
+        if let Some(self_ident) = idents.iter().find(|ident| *ident == "self") {
+            return Err(Error::new(
+                self_ident.span(),
+                "`self` can't be used in a default, since it would refer to the builder and not the struct - \
+                 to use the value of another field, refer to it by its name (e.g. `default = other_field + 1`, or \
+                 `default_code = \"other_field + 1\"`)",
+            ));
+        }
+        self.default = Some(default);
+        self.default_set_at = Some(span);
+        self.type_default = false;
+        Ok(())
+    }
+
+    fn skip_with_type_default(&mut self, span: Span) {
+        self.default = Some(syn::parse2(quote!(::core::default::Default::default())).unwrap());
+        self.type_default = true;
+        self.setter.skip = Some(span);
+    }
+
+    /// Makes a type default refer to `Default` through `core` instead of `::core`.
+    pub fn set_core_path(&mut self, core: &syn::Path) {
+        if self.type_default {
+            self.default = Some(syn::parse2(quote!(#core::default::Default::default())).unwrap());
+        }
+    }
+
     fn inter_fields_conflicts(&self) -> Result<(), Error> {
         if let (Some(skip), None) = (&self.setter.skip, &self.default) {
             return Err(Error::new(
                 *skip,
-                "#[builder(skip)] must be accompanied by default or default_code",
+                "#[builder(setter(skip))] must be accompanied by default or default_code, since the field's value can't be set",
             ));
         }
 
+        if let (Some(transform), Some(auto_into)) = (&self.setter.transform, &self.setter.auto_into) {
+            let mut error = Error::new(transform.span, "transform conflicts with into");
+            error.combine(Error::new(*auto_into, "into set here"));
+            return Err(error);
+        }
+
+        // `try_into`, `from_ref`, `into_iter` and `nested` replace how the argument is turned into the field's
+        // value, so they conflict with each other as well.
+        let conversions = [
+            ("try_into", self.setter.try_into),
+            ("from_ref", self.setter.from_ref),
+            ("into_iter", self.setter.into_iter),
+            ("nested", self.setter.nested),
+        ];
+        let wrap_with_span = self.setter.wrap_with.as_ref().map(Spanned::span);
+        let conflicting = [
+            ("into", self.setter.auto_into),
+            ("transform", self.setter.transform.as_ref().map(|t| t.span)),
+            ("wrap_with", wrap_with_span),
+            ("strip_bool", self.setter.strip_bool),
+        ];
+        for (i, (name, conversion)) in conversions.iter().enumerate() {
+            let Some(conversion) = conversion else {
+                continue;
+            };
+            if let Some((caption, span)) = conflicting
+                .iter()
+                .chain(&conversions[i + 1..])
+                .find_map(|(caption, span)| span.map(|span| (caption, span)))
+            {
+                let mut error = Error::new(*conversion, format_args!("{} conflicts with {}", name, caption));
+                error.combine(Error::new(span, format_args!("{} set here", caption)));
+                return Err(error);
+            }
+        }
+
         let conflicting_transformations = [
             ("transform", self.setter.transform.as_ref().map(|t| &t.span)),
+            ("wrap_with", wrap_with_span.as_ref()),
             ("strip_option", self.setter.strip_option.as_ref()),
             ("strip_bool", self.setter.strip_bool.as_ref()),
+            ("auto_box", self.setter.auto_box.as_ref()),
+            ("auto_arc", self.setter.auto_arc.as_ref()),
+            ("auto_rc", self.setter.auto_rc.as_ref()),
         ];
         let mut conflicting_transformations = conflicting_transformations
             .iter()
@@ -295,6 +609,27 @@ impl SetterSettings {
             syn::Expr::Assign(assign) => {
                 let name =
                     expr_to_single_string(&assign.left).ok_or_else(|| Error::new_spanned(&assign.left, "Expected identifier"))?;
+                // These may come from `field_defaults`, so only repetitions on the field itself are errors.
+                const SINGLE_VALUED: [&str; 9] = [
+                    "doc",
+                    "transform",
+                    "wrap_with",
+                    "validate",
+                    "name",
+                    "each",
+                    "prefix",
+                    "vis",
+                    "suffix",
+                ];
+                if let Some(key) = SINGLE_VALUED.into_iter().find(|key| *key == name) {
+                    if self.flags_set_here.contains(&key) {
+                        return Err(Error::new_spanned(
+                            &assign.left,
+                            format!("Illegal setting - `{}` is already set", key),
+                        ));
+                    }
+                    self.flags_set_here.push(key);
+                }
                 match name.as_str() {
                     "doc" => {
                         self.doc = Some(*assign.right);
@@ -304,15 +639,97 @@ impl SetterSettings {
                         self.transform = Some(parse_transform_closure(assign.left.span(), *assign.right)?);
                         Ok(())
                     }
+                    "validate" => {
+                        self.validate = Some(parse_validator_closure(*assign.right)?);
+                        Ok(())
+                    }
+                    "into" => {
+                        if self.flags_set_here.contains(&"into") {
+                            return Err(Error::new(
+                                assign.left.span(),
+                                "Illegal setting - field is already calling into() on the argument",
+                            ));
+                        }
+                        self.flags_set_here.push("into");
+                        let expr_path = match *assign.right {
+                            syn::Expr::Path(expr_path) => expr_path,
+                            // `into = true` and `into = false` are the same as `into` and `!into`.
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Bool(enabled),
+                                ..
+                            }) => {
+                                self.auto_into = enabled.value.then(|| assign.left.span());
+                                self.into_type = None;
+                                return Ok(());
+                            }
+                            right => return Err(Error::new_spanned(right, "Expected path expression type")),
+                        };
+                        self.auto_into = Some(assign.left.span());
+                        self.into_type = Some(syn::Type::Path(syn::TypePath {
+                            qself: expr_path.qself,
+                            path: expr_path.path,
+                        }));
+                        Ok(())
+                    }
+                    "nested" => {
+                        if self.flags_set_here.contains(&"nested") {
+                            return Err(Error::new(
+                                assign.left.span(),
+                                "Illegal setting - field is already configured with a nested builder",
+                            ));
+                        }
+                        self.flags_set_here.push("nested");
+                        let syn::Expr::Path(expr_path) = *assign.right else {
+                            return Err(Error::new_spanned(assign.right, "Expected path expression type"));
+                        };
+                        self.nested = Some(assign.left.span());
+                        self.nested_builder = Some(syn::Type::Path(syn::TypePath {
+                            qself: expr_path.qself,
+                            path: expr_path.path,
+                        }));
+                        self.nested_builder_method = None;
+                        Ok(())
+                    }
+                    "name" => {
+                        let name = expr_to_lit_string(&assign.right)?;
+                        if name.is_empty() {
+                            return Err(Error::new_spanned(&assign.right, "setter name cannot be empty"));
+                        }
+                        self.name =
+                            Some(parse_setter_ident(&name, assign.right.span()).map_err(|_| {
+                                Error::new_spanned(&assign.right, format!("{:?} is not a valid identifier", name))
+                            })?);
+                        Ok(())
+                    }
+                    "each" => {
+                        let name = expr_to_lit_string(&assign.right)?;
+                        self.each =
+                            Some(parse_setter_ident(&name, assign.right.span()).map_err(|_| {
+                                Error::new_spanned(&assign.right, format!("{:?} is not a valid identifier", name))
+                            })?);
+                        Ok(())
+                    }
                     "prefix" => {
-                        self.prefix = Some(expr_to_lit_string(&*assign.right)?);
+                        self.prefix = Some(expr_to_lit_string(&assign.right)?);
+                        Ok(())
+                    }
+                    "vis" => {
+                        let vis = expr_to_lit_string(&assign.right)?;
+                        self.vis =
+                            Some(syn::parse_str(&vis).map_err(|_| {
+                                Error::new_spanned(&assign.right, format!("{:?} is not a valid visibility", vis))
+                            })?);
                         Ok(())
                     }
                     "suffix" => {
-                        self.suffix = Some(expr_to_lit_string(&*assign.right)?);
+                        self.suffix = Some(expr_to_lit_string(&assign.right)?);
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(&assign, format!("Unknown parameter {:?}", name))),
+                    "wrap_with" => {
+                        self.wrap_with = Some(*assign.right);
+                        Ok(())
+                    }
+                    _ => Err(unknown_key_error(&assign.left, SETTER_KEYS)),
                 }
             }
             syn::Expr::Path(path) => {
@@ -322,27 +739,36 @@ impl SetterSettings {
                         match name.as_str() {
                             $(
                                 $flag => {
-                                    if self.$field.is_some() {
+                                    if self.flags_set_here.contains(&$flag) {
                                         Err(Error::new(path.span(), concat!("Illegal setting - field is already ", $already)))
                                     } else {
                                         $checks;
+                                        self.flags_set_here.push($flag);
                                         self.$field = Some(path.span());
                                         Ok(())
                                     }
                                 }
                             )*
-                            _ => Err(Error::new_spanned(
-                                    &path,
-                                    format!("Unknown setter parameter {:?}", name),
-                            ))
+                            _ => Err(unknown_key_error(&path, SETTER_KEYS)),
                         }
                     }
                 }
                 handle_fields!(
                     "skip", skip, "skipped", {};
-                    "into", auto_into, "calling into() on the argument", {};
+                    "into", auto_into, "calling into() on the argument", { self.into_type = None };
                     "strip_option", strip_option, "putting the argument in Some(...)", {};
                     "strip_bool", strip_bool, "zero arguments setter, sets the field to true", {};
+                    "try_into", try_into, "calling try_into() on the argument", {};
+                    "auto_box", auto_box, "putting the argument in a Box", {};
+                    "auto_arc", auto_arc, "putting the argument in an Arc", {};
+                    "auto_rc", auto_rc, "putting the argument in an Rc", {};
+                    "from_ref", from_ref, "cloning the argument from a reference", {};
+                    "into_iter", into_iter, "collecting the argument from an iterator", {};
+                    "nested", nested, "configured with a nested builder", {
+                        self.nested_builder = None;
+                        self.nested_builder_method = None;
+                    };
+                    "mutable", mutable, "a mutable setter", {};
                 )
             }
             syn::Expr::Unary(syn::ExprUnary {
@@ -362,8 +788,9 @@ impl SetterSettings {
                             self.skip = None;
                             Ok(())
                         }
-                        "auto_into" => {
+                        "into" | "auto_into" => {
                             self.auto_into = None;
+                            self.into_type = None;
                             Ok(())
                         }
                         "strip_option" => {
@@ -374,17 +801,115 @@ impl SetterSettings {
                             self.strip_bool = None;
                             Ok(())
                         }
-                        _ => Err(Error::new_spanned(path, "Unknown setting".to_owned())),
+                        "try_into" => {
+                            self.try_into = None;
+                            Ok(())
+                        }
+                        "auto_box" => {
+                            self.auto_box = None;
+                            Ok(())
+                        }
+                        "auto_arc" => {
+                            self.auto_arc = None;
+                            Ok(())
+                        }
+                        "auto_rc" => {
+                            self.auto_rc = None;
+                            Ok(())
+                        }
+                        "from_ref" => {
+                            self.from_ref = None;
+                            Ok(())
+                        }
+                        "into_iter" => {
+                            self.into_iter = None;
+                            Ok(())
+                        }
+                        "nested" => {
+                            self.nested = None;
+                            self.nested_builder = None;
+                            self.nested_builder_method = None;
+                            Ok(())
+                        }
+                        "mutable" => {
+                            self.mutable = None;
+                            Ok(())
+                        }
+                        _ => Err(unknown_key_error(&path, SETTER_NEGATABLE_KEYS)),
                     }
                 } else {
                     Err(Error::new_spanned(expr, "Expected simple identifier".to_owned()))
                 }
             }
+            syn::Expr::Call(call) => {
+                let name =
+                    expr_to_single_string(&call.func).ok_or_else(|| Error::new_spanned(&call.func, "Expected identifier"))?;
+                if name != "nested" {
+                    return Err(unknown_key_error(&call.func, SETTER_KEYS));
+                }
+                if self.flags_set_here.contains(&"nested") {
+                    return Err(Error::new_spanned(
+                        &call.func,
+                        "Illegal setting - field is already configured with a nested builder",
+                    ));
+                }
+                self.flags_set_here.push("nested");
+                self.nested = Some(call.func.span());
+                self.nested_builder = None;
+                self.nested_builder_method = None;
+                for arg in call.args {
+                    let syn::Expr::Assign(assign) = arg else {
+                        return Err(Error::new_spanned(arg, "Expected (<...>=<...>)"));
+                    };
+                    let name = expr_to_single_string(&assign.left)
+                        .ok_or_else(|| Error::new_spanned(&assign.left, "Expected identifier"))?;
+                    let syn::Expr::Path(expr_path) = *assign.right else {
+                        return Err(Error::new_spanned(assign.right, "Expected path expression type"));
+                    };
+                    match name.as_str() {
+                        "builder_type" => {
+                            ensure_not_set(&self.nested_builder, &assign.left)?;
+                            self.nested_builder = Some(syn::Type::Path(syn::TypePath {
+                                qself: expr_path.qself,
+                                path: expr_path.path,
+                            }));
+                        }
+                        "builder" => {
+                            ensure_not_set(&self.nested_builder_method, &assign.left)?;
+                            self.nested_builder_method = Some(expr_path);
+                        }
+                        _ => return Err(unknown_key_error(&assign.left, NESTED_KEYS)),
+                    }
+                }
+                Ok(())
+            }
             _ => Err(Error::new_spanned(expr, "Expected (<...>=<...>)")),
         }
     }
 }
 
+/// Whether the field's own `#[builder(...)]` attributes have `setter(...)` settings.
+fn configures_setter(attrs: &[syn::Attribute]) -> Result<bool, Error> {
+    let mut configures_setter = false;
+    for attr in attrs {
+        let syn::Meta::List(list) = &attr.meta else {
+            continue;
+        };
+        if path_to_single_string(&list.path).as_deref() != Some("builder") {
+            continue;
+        }
+        apply_subsections(list, |expr| {
+            if let syn::Expr::Call(call) = &expr {
+                if expr_to_single_string(&call.func).as_deref() == Some("setter") && !call.args.is_empty() {
+                    configures_setter = true;
+                }
+            }
+            Ok(())
+        })?;
+    }
+    Ok(configures_setter)
+}
+
 #[derive(Debug, Clone)]
 pub struct Transform {
     pub params: Vec<(syn::Pat, syn::Type)>,
@@ -419,3 +944,58 @@ fn parse_transform_closure(span: Span, expr: syn::Expr) -> Result<Transform, Err
         span,
     })
 }
+
+#[derive(Debug, Clone)]
+pub struct Validator {
+    pub closure: syn::ExprClosure,
+    pub error_type: syn::Type,
+}
+
+fn parse_validator_closure(expr: syn::Expr) -> Result<Validator, Error> {
+    let closure = match expr {
+        syn::Expr::Closure(closure) => closure,
+        _ => return Err(Error::new_spanned(expr, "Expected closure")),
+    };
+    if let Some(kw) = &closure.asyncness {
+        return Err(Error::new(kw.span, "Validator closure cannot be async"));
+    }
+    if closure.inputs.len() != 1 {
+        return Err(Error::new_spanned(
+            &closure.inputs,
+            "Validator closure must accept exactly one argument",
+        ));
+    }
+
+    let error_type = match &closure.output {
+        syn::ReturnType::Type(_, ty) => match &**ty {
+            syn::Type::Path(type_path) => type_path.path.segments.last().and_then(|segment| {
+                if segment.ident != "Result" {
+                    return None;
+                }
+                match &segment.arguments {
+                    syn::PathArguments::AngleBracketed(args) if args.args.len() == 2 => match &args.args[1] {
+                        syn::GenericArgument::Type(error_type) => Some(error_type.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }),
+            _ => None,
+        },
+        syn::ReturnType::Default => None,
+    }
+    .ok_or_else(|| {
+        Error::new_spanned(
+            &closure,
+            "Validator closure must explicitly declare its return type as `Result<(), ErrorType>`",
+        )
+    })?;
+
+    Ok(Validator { closure, error_type })
+}
+
+fn parse_setter_ident(name: &str, span: Span) -> Result<Ident, Error> {
+    let mut ident: Ident = syn::parse_str(name)?;
+    ident.set_span(span);
+    Ok(ident)
+}
@@ -1,8 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse::Error, parse_macro_input, spanned::Spanned, DeriveInput};
+use syn::{parse::Error, parse_macro_input, DeriveInput};
 
 mod field_info;
+mod mutator;
 mod struct_info;
 mod util;
 
@@ -17,34 +18,192 @@ pub fn derive_typed_builder(input: proc_macro::TokenStream) -> proc_macro::Token
 
 fn impl_my_derive(ast: &syn::DeriveInput) -> Result<TokenStream, Error> {
     let data = match &ast.data {
-        syn::Data::Struct(data) => match &data.fields {
-            syn::Fields::Named(fields) => {
-                let struct_info = struct_info::StructInfo::new(ast, fields.named.iter())?;
-                let builder_creation = struct_info.builder_creation_impl()?;
-                let conversion_helper = struct_info.conversion_helper_impl();
-                let fields = struct_info
-                    .included_fields()
-                    .map(|f| struct_info.field_impl(f))
-                    .collect::<Result<TokenStream, _>>()?;
-                let required_fields = struct_info
-                    .included_fields()
-                    .filter(|f| f.builder_attr.default.is_none())
-                    .map(|f| struct_info.required_field_impl(f));
-                let build_method = struct_info.build_method_impl();
-
-                quote! {
-                    #builder_creation
-                    #conversion_helper
-                    #fields
-                    #(#required_fields)*
-                    #build_method
-                }
+        syn::Data::Struct(data) => {
+            let struct_info = struct_info::StructInfo::new(ast, data.fields.iter())?;
+            struct_info.builder_module_impl(builder_impl(&struct_info)?)
+        }
+        // Each variant gets a builder of its own, and they all go in the same module.
+        syn::Data::Enum(data) => {
+            let struct_infos = data
+                .variants
+                .iter()
+                .map(|variant| struct_info::StructInfo::new_for_variant(ast, variant))
+                .collect::<Result<Vec<_>, _>>()?;
+            let builders = struct_infos.iter().map(builder_impl).collect::<Result<TokenStream, _>>()?;
+            match struct_infos.first() {
+                Some(struct_info) => struct_info.builder_module_impl(builders),
+                None => builders,
             }
-            syn::Fields::Unnamed(_) => return Err(Error::new(ast.span(), "TypedBuilder is not supported for tuple structs")),
-            syn::Fields::Unit => return Err(Error::new(ast.span(), "TypedBuilder is not supported for unit structs")),
-        },
-        syn::Data::Enum(_) => return Err(Error::new(ast.span(), "TypedBuilder is not supported for enums")),
-        syn::Data::Union(_) => return Err(Error::new(ast.span(), "TypedBuilder is not supported for unions")),
+        }
+        syn::Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "TypedBuilder is not supported for unions - only for structs and enums",
+            ))
+        }
     };
     Ok(data)
 }
+
+fn builder_impl(struct_info: &struct_info::StructInfo) -> Result<TokenStream, Error> {
+    let builder_creation = struct_info.builder_creation_impl()?;
+    let fields = struct_info
+        .included_fields()
+        .filter(|f| f.has_setter())
+        .map(|f| struct_info.field_impl(f))
+        .collect::<Result<TokenStream, _>>()?;
+    let required_fields = struct_info
+        .included_fields()
+        .filter(|f| f.is_required())
+        .map(|f| struct_info.required_field_impl(f));
+    let getters = if struct_info.builder_attr.getters {
+        struct_info
+            .included_fields()
+            .map(|f| struct_info.getter_impl(f))
+            .collect::<TokenStream>()
+    } else {
+        TokenStream::new()
+    };
+    let mutators = struct_info
+        .builder_attr
+        .mutators
+        .iter()
+        .map(|m| struct_info.mutator_impl(m))
+        .collect::<Result<TokenStream, _>>()?;
+    let build_method = struct_info.build_method_impl()?;
+
+    let output = quote! {
+        #builder_creation
+        #fields
+        #(#required_fields)*
+        #getters
+        #mutators
+        #build_method
+    };
+    Ok(if let Some(cfg) = &struct_info.builder_attr.cfg {
+        let items = syn::parse2::<syn::File>(output)?.items;
+        quote!(#( #[cfg(#cfg)] #items )*)
+    } else {
+        output
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::visit::Visit;
+
+    /// The attributes of each method with the given name that the derive generates for the input.
+    fn method_attrs(input: TokenStream, name: &str) -> Vec<Vec<syn::Attribute>> {
+        struct MethodCollector<'a> {
+            name: &'a str,
+            attrs: Vec<Vec<syn::Attribute>>,
+        }
+
+        impl<'ast> Visit<'ast> for MethodCollector<'_> {
+            fn visit_impl_item_fn(&mut self, method: &'ast syn::ImplItemFn) {
+                if method.sig.ident == self.name {
+                    self.attrs.push(method.attrs.clone());
+                }
+                syn::visit::visit_impl_item_fn(self, method);
+            }
+        }
+
+        let ast = syn::parse2(input).unwrap();
+        let output = syn::parse2::<syn::File>(impl_my_derive(&ast).unwrap()).unwrap();
+        let mut collector = MethodCollector { name, attrs: Vec::new() };
+        collector.visit_file(&output);
+        assert!(!collector.attrs.is_empty(), "no method named `{}` was generated", name);
+        collector.attrs
+    }
+
+    fn docs(attrs: &[syn::Attribute]) -> Vec<String> {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .map(|attr| match &attr.meta.require_name_value().unwrap().value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(doc), ..
+                }) => doc.value(),
+                value => panic!("unexpected doc: {}", value.to_token_stream()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_setter_inherits_the_field_docs() {
+        let input = quote! {
+            struct Foo {
+                /// The x coordinate.
+                x: i32,
+                /// Not used for `y`.
+                #[builder(setter(doc = "Sets `y`."))]
+                y: i32,
+            }
+        };
+        assert_eq!(docs(&method_attrs(input.clone(), "x")[0]), [" The x coordinate."]);
+        assert_eq!(docs(&method_attrs(input, "y")[0]), ["Sets `y`."]);
+    }
+
+    #[test]
+    fn test_inline() {
+        let is_inline = |attrs: &Vec<syn::Attribute>| attrs.iter().any(|attr| attr.path().is_ident("inline"));
+        let input = quote! {
+            #[builder(inline)]
+            struct Foo {
+                x: i32,
+                #[builder(default, setter(each = "item"))]
+                items: Vec<i32>,
+            }
+        };
+        for method in ["x", "items", "item", "build"] {
+            assert!(
+                method_attrs(input.clone(), method).iter().any(is_inline),
+                "`{}` is not inline",
+                method
+            );
+        }
+
+        let input = quote! {
+            struct Foo {
+                x: i32,
+            }
+        };
+        for method in ["x", "build"] {
+            assert!(
+                !method_attrs(input.clone(), method).iter().any(is_inline),
+                "`{}` is inline",
+                method
+            );
+        }
+    }
+
+    #[test]
+    fn test_renamed_setters_get_a_doc_alias() {
+        let doc_aliases = |attrs: &Vec<syn::Attribute>| {
+            attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("doc"))
+                .filter_map(|attr| attr.parse_args::<syn::MetaNameValue>().ok())
+                .filter(|meta| meta.path.is_ident("alias"))
+                .map(|meta| meta.value.to_token_stream().to_string())
+                .collect::<Vec<_>>()
+        };
+        let input = quote! {
+            #[builder(field_defaults(setter(prefix = "with_")))]
+            struct Foo {
+                x: i32,
+                #[builder(setter(name = "set_y"))]
+                y: i32,
+                #[builder(setter(name = "z"))]
+                z: i32,
+                r#type: i32,
+            }
+        };
+        assert_eq!(doc_aliases(&method_attrs(input.clone(), "with_x")[0]), ["\"x\""]);
+        assert_eq!(doc_aliases(&method_attrs(input.clone(), "set_y")[0]), ["\"y\""]);
+        assert_eq!(doc_aliases(&method_attrs(input.clone(), "z")[0]), Vec::<String>::new());
+        assert_eq!(doc_aliases(&method_attrs(input, "with_type")[0]), ["\"type\""]);
+    }
+}
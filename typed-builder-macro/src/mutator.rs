@@ -0,0 +1,96 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    parse::{Error, Parse, ParseStream},
+    spanned::Spanned,
+};
+
+#[derive(Debug, Clone)]
+pub struct Mutator {
+    pub fun: syn::ItemFn,
+    /// Names of the fields accessed via `self.<field>` inside the mutator's body.
+    pub accessed_fields: Vec<syn::Ident>,
+}
+
+impl Parse for Mutator {
+    fn parse(input: ParseStream) -> Result<Self, Error> {
+        let fun: syn::ItemFn = input.parse()?;
+
+        match fun.sig.inputs.first() {
+            Some(syn::FnArg::Receiver(receiver)) if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+            Some(arg) => return Err(Error::new_spanned(arg, "mutator must take `&mut self` as its first argument")),
+            None => {
+                return Err(Error::new(
+                    fun.sig.span(),
+                    "mutator must take `&mut self` as its first argument",
+                ))
+            }
+        }
+        if let Some(kw) = &fun.sig.asyncness {
+            return Err(Error::new(kw.span, "mutator cannot be async"));
+        }
+        if !matches!(fun.sig.output, syn::ReturnType::Default) {
+            return Err(Error::new_spanned(&fun.sig.output, "mutator cannot return a value"));
+        }
+
+        let mut accessed_fields = Vec::new();
+        collect_self_fields(fun.block.to_token_stream(), &mut accessed_fields);
+
+        Ok(Mutator { fun, accessed_fields })
+    }
+}
+
+impl Mutator {
+    pub fn accesses(&self, field_name: &syn::Ident) -> bool {
+        self.accessed_fields.iter().any(|accessed| accessed == field_name)
+    }
+}
+
+/// Parses a `mutators(...)` group - a list of `fn` items, optionally separated by commas.
+pub fn parse_mutators(input: ParseStream) -> Result<Vec<Mutator>, Error> {
+    let name: syn::Ident = input.parse()?;
+    if name != "mutators" {
+        return Err(Error::new_spanned(name, "Expected mutators(...)"));
+    }
+    let content;
+    syn::parenthesized!(content in input);
+    let input = &content;
+
+    let mut mutators = Vec::new();
+    while !input.is_empty() {
+        mutators.push(input.parse()?);
+        if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+        }
+    }
+    Ok(mutators)
+}
+
+/// Collects the `<field>` part of every `self.<field>` expression in the token stream. Method calls
+/// (`self.<method>(...)` or `self.<method>::<...>(...)`) are not field accesses.
+fn collect_self_fields(tokens: TokenStream, result: &mut Vec<syn::Ident>) {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => collect_self_fields(group.stream(), result),
+            TokenTree::Ident(ident) if ident == "self" => {
+                if !matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '.') {
+                    continue;
+                }
+                tokens.next();
+                let Some(TokenTree::Ident(field)) = tokens.next_if(|token| matches!(token, TokenTree::Ident(_))) else {
+                    continue;
+                };
+                let is_method_call = match tokens.peek() {
+                    Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Parenthesis,
+                    Some(TokenTree::Punct(punct)) => punct.as_char() == ':',
+                    _ => false,
+                };
+                if !is_method_call && !result.contains(&field) {
+                    result.push(field);
+                }
+            }
+            _ => {}
+        }
+    }
+}
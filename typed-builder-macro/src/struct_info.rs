@@ -1,24 +1,31 @@
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
-use syn::parse::Error;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::parse::{Error, Parser};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
 
 use crate::field_info::{FieldBuilderAttr, FieldInfo};
+use crate::mutator::{parse_mutators, Mutator};
 use crate::util::{
-    apply_subsections, empty_type, empty_type_tuple, expr_to_single_string, first_visibility, make_punctuated_single,
-    modify_types_generics_hack, path_to_single_string, public_visibility, strip_raw_ident_prefix, type_tuple,
+    apply_subsections, collect_variable_idents, default_builder_type, empty_type, empty_type_tuple, ensure_not_set,
+    expr_to_lit_string, expr_to_single_string, extract_wrapped_type, first_visibility, is_repr_packed,
+    modify_types_generics_hack, parse_bounds, parse_extra_impl, path_to_single_string, path_without_generic_arguments,
+    strip_raw_ident_prefix, to_snake_case, type_tuple, unknown_key_error, visibility_from_submodule, RenameRule,
 };
 
 #[derive(Debug)]
 pub struct StructInfo<'a> {
     pub vis: &'a syn::Visibility,
     pub name: &'a syn::Ident,
+    /// The enum variant the builder builds, when deriving for an enum.
+    pub variant: Option<&'a syn::Ident>,
     pub generics: &'a syn::Generics,
     pub fields: Vec<FieldInfo<'a>>,
 
     pub builder_attr: TypeBuilderAttr<'a>,
     pub builder_name: syn::Ident,
-    pub conversion_helper_trait_name: syn::Ident,
-    pub core: syn::Ident,
+    /// Whether the struct is `#[repr(packed)]`, so its fields may be misaligned.
+    pub is_packed: bool,
 }
 
 impl<'a> StructInfo<'a> {
@@ -27,28 +34,269 @@ impl<'a> StructInfo<'a> {
     }
 
     pub fn new(ast: &'a syn::DeriveInput, fields: impl Iterator<Item = &'a syn::Field>) -> Result<StructInfo<'a>, Error> {
-        let builder_attr = TypeBuilderAttr::new(&ast.attrs)?;
+        Self::new_impl(ast, None, &ast.attrs, fields)
+    }
+
+    /// A builder for a variant of an enum. The `#[builder(...)]` attributes of the variant apply on
+    /// top of those of the enum.
+    pub fn new_for_variant(ast: &'a syn::DeriveInput, variant: &'a syn::Variant) -> Result<StructInfo<'a>, Error> {
+        if !matches!(variant.fields, syn::Fields::Named(_)) {
+            return Err(Error::new_spanned(
+                &variant.ident,
+                "TypedBuilder only supports enum variants with named fields",
+            ));
+        }
+        // Each variant needs a builder method and a builder type of its own, so they can't be named
+        // on the enum.
+        let enum_attr = TypeBuilderAttr::new(&ast.attrs)?;
+        for (setting, name) in [
+            ("builder_method", &enum_attr.builder_method.name),
+            ("builder_type", &enum_attr.builder_type.name),
+        ] {
+            if let Some(name) = name {
+                return Err(Error::new_spanned(
+                    name,
+                    format!(
+                        "`{}(name = ...)` would give all the variants the same name - set it on each variant instead",
+                        setting
+                    ),
+                ));
+            }
+        }
+        let attrs = ast.attrs.iter().chain(&variant.attrs).cloned().collect::<Vec<_>>();
+        let result = Self::new_impl(ast, Some(&variant.ident), &attrs, variant.fields.iter())?;
+        // These generate items on the enum itself, which would clash between the variants.
+        for (setting, enabled) in [
+            ("into_builder", result.builder_attr.into_builder),
+            ("field_metadata", result.builder_attr.field_metadata),
+            ("expose_defaults", result.builder_attr.expose_defaults),
+        ] {
+            if enabled {
+                return Err(Error::new_spanned(
+                    &variant.ident,
+                    format!("`{}` is not supported for enums", setting),
+                ));
+            }
+        }
+        // The fields of a variant can't be written in place one by one.
+        if result.builder_attr.build_into {
+            return Err(Error::new_spanned(&variant.ident, "`build_into` is not supported for enums"));
+        }
+        if let Some(span) = result.builder_attr.test_builder {
+            return Err(Error::new(span, "`test_builder` is not supported for enums"));
+        }
+        Ok(result)
+    }
+
+    fn new_impl(
+        ast: &'a syn::DeriveInput,
+        variant: Option<&'a syn::Ident>,
+        attrs: &[syn::Attribute],
+        fields: impl Iterator<Item = &'a syn::Field>,
+    ) -> Result<StructInfo<'a>, Error> {
+        let builder_attr = TypeBuilderAttr::new(attrs)?;
         let builder_name = builder_attr
             .builder_type
             .get_name()
             .map(|name| strip_raw_ident_prefix(name.to_string()))
-            .unwrap_or_else(|| strip_raw_ident_prefix(format!("{}Builder", ast.ident)));
-        Ok(StructInfo {
+            .unwrap_or_else(|| {
+                let variant = variant.map_or_else(String::new, |variant| strip_raw_ident_prefix(variant.to_string()));
+                strip_raw_ident_prefix(format!("{}{}Builder", ast.ident, variant))
+            });
+        let mut result = StructInfo {
             vis: &ast.vis,
             name: &ast.ident,
+            variant,
             generics: &ast.generics,
             fields: fields
                 .enumerate()
-                .map(|(i, f)| FieldInfo::new(i, f, builder_attr.field_defaults.clone()))
-                .collect::<Result<_, _>>()?,
+                .map(|(i, f)| {
+                    let mut field = FieldInfo::new(i, f, builder_attr.field_defaults.clone())?;
+                    if let Some(core) = &builder_attr.crate_module {
+                        field.builder_attr.set_core_path(core);
+                    }
+                    Ok(field)
+                })
+                .collect::<Result<_, Error>>()?,
             builder_attr,
             builder_name: syn::Ident::new(&builder_name, proc_macro2::Span::call_site()),
-            conversion_helper_trait_name: syn::Ident::new(&format!("{}_Optional", builder_name), proc_macro2::Span::call_site()),
-            core: syn::Ident::new(&format!("{}_core", builder_name), proc_macro2::Span::call_site()),
-        })
+            is_packed: is_repr_packed(&ast.attrs),
+        };
+        result.rename_clashing_generic_idents();
+        result.check_setter_names()?;
+        result.make_setters_mutable_for_merge()?;
+        result.check_groups()?;
+        result.check_test_builder()?;
+        result.check_exposed_default_names()?;
+        Ok(result)
+    }
+
+    /// The type-state generic of each field is named after the field (e.g. `__x`), which may clash
+    /// with the struct's own generic parameters, so these get renamed until they don't.
+    fn rename_clashing_generic_idents(&mut self) {
+        let struct_generics = self
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                syn::GenericParam::Type(ty) => Some(&ty.ident),
+                syn::GenericParam::Const(cnst) => Some(&cnst.ident),
+                syn::GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        for i in 0..self.fields.len() {
+            let mut name = self.fields[i].generic_ident.to_string();
+            while struct_generics.iter().any(|ident| **ident == name)
+                || self.fields.iter().enumerate().any(|(j, f)| j != i && f.generic_ident == name)
+            {
+                name.push('_');
+            }
+            self.fields[i].generic_ident = syn::Ident::new(&name, proc_macro2::Span::call_site());
+        }
+    }
+
+    /// An identifier for a generic parameter of a builder method, which clashes neither with the
+    /// struct's generic parameters nor with the type-state ones of the fields.
+    fn unused_generic_ident(&self, name: &str) -> syn::Ident {
+        let mut name = name.to_owned();
+        while self.generics.params.iter().any(|param| match param {
+            syn::GenericParam::Type(ty) => ty.ident == name,
+            syn::GenericParam::Const(cnst) => cnst.ident == name,
+            syn::GenericParam::Lifetime(_) => false,
+        }) || self.fields.iter().any(|f| f.generic_ident == name)
+        {
+            name.push('_');
+        }
+        syn::Ident::new(&name, proc_macro2::Span::call_site())
+    }
+
+    /// A `mergeable` builder stores all the fields as `Option`s, just like `setter(mutable)` does, so
+    /// that the builder's type doesn't depend on which fields are set.
+    fn make_setters_mutable_for_merge(&mut self) -> Result<(), Error> {
+        if !self.builder_attr.mergeable {
+            return Ok(());
+        }
+        for field in self.fields.iter_mut().filter(|f| f.has_setter()) {
+            if field.builder_attr.default.is_none() {
+                return Err(Error::new_spanned(
+                    &field.name,
+                    "`mergeable` requires every field with a setter to have a default",
+                ));
+            }
+            if let Some(each) = &field.builder_attr.setter.each {
+                return Err(Error::new_spanned(each, "`each` can't be used in a `mergeable` builder"));
+            }
+            field.builder_attr.setter.mutable.get_or_insert(field.name.span());
+        }
+        Ok(())
+    }
+
+    /// The fields of each `group = "..."`, with the groups in the order they first appear.
+    fn field_groups(&self) -> Vec<(&syn::Ident, Vec<&FieldInfo<'a>>)> {
+        let mut groups: Vec<(&syn::Ident, Vec<&FieldInfo<'a>>)> = Vec::new();
+        for field in &self.fields {
+            let Some(group) = &field.builder_attr.group else {
+                continue;
+            };
+            match groups.iter_mut().find(|(name, _)| *name == group) {
+                Some((_, fields)) => fields.push(field),
+                None => groups.push((group, vec![field])),
+            }
+        }
+        groups
+    }
+
+    /// Makes sure that groups only have fields that may be left unset, and that their error variants
+    /// don't clash with those of the validators.
+    fn check_groups(&self) -> Result<(), Error> {
+        for (group, fields) in self.field_groups() {
+            if let Some(field) = fields.iter().find(|f| !f.has_setter() || f.builder_attr.default.is_none()) {
+                return Err(Error::new_spanned(
+                    &field.name,
+                    "the fields of a group must have a setter and a default",
+                ));
+            }
+            if fields.len() < 2 {
+                return Err(Error::new_spanned(
+                    group,
+                    format!("group `{}` has only one field", strip_raw_ident_prefix(group.to_string())),
+                ));
+            }
+            if self
+                .fields
+                .iter()
+                .any(|f| f.builder_attr.setter.validate.is_some() && f.name == *group)
+            {
+                return Err(Error::new_spanned(
+                    group,
+                    format!(
+                        "group `{}` has the same name as a field with a validator",
+                        strip_raw_ident_prefix(group.to_string())
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes sure that with `test_builder`, a builder that no setter was called on can be built.
+    fn check_test_builder(&self) -> Result<(), Error> {
+        let Some(span) = self.builder_attr.test_builder else {
+            return Ok(());
+        };
+        let required_fields = self
+            .fields
+            .iter()
+            .filter(|f| f.builder_attr.default.is_none() && f.builder_attr.via_mutators.is_none())
+            .map(|f| format!("`{}`", strip_raw_ident_prefix(f.name.to_string())))
+            .collect::<Vec<_>>();
+        if required_fields.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                span,
+                format!(
+                    "`test_builder` requires all the fields to have a default - missing for {}",
+                    required_fields.join(", ")
+                ),
+            ))
+        }
+    }
+
+    /// Makes sure no two setters - including `each` setters - share a name.
+    fn check_setter_names(&self) -> Result<(), Error> {
+        let mut seen: Vec<syn::Ident> = Vec::new();
+        for field in self.included_fields().filter(|f| f.has_setter()) {
+            // Other keywords can be used as raw identifiers, but not these.
+            if let Some(name) = field.renamed_setter_name().filter(|name| {
+                field.builder_attr.setter.name.is_none() && ["crate", "self", "super", "Self"].contains(&name.as_str())
+            }) {
+                return Err(Error::new_spanned(
+                    &field.name,
+                    format!(
+                        "the setter would be named `{}`, which can't be a raw identifier - name it with `setter(name = ...)`",
+                        name
+                    ),
+                ));
+            }
+            let mut setter_name = field.setter_method_name();
+            if field.builder_attr.setter.name.is_none() {
+                setter_name.set_span(field.name.span());
+            }
+            for name in std::iter::once(setter_name).chain(field.builder_attr.setter.each.clone()) {
+                if let Some(previous) = seen.iter().find(|previous| **previous == name) {
+                    let mut error = Error::new(name.span(), format!("duplicate setter name `{}`", name));
+                    error.combine(Error::new(previous.span(), "first used here"));
+                    return Err(error);
+                }
+                seen.push(name);
+            }
+        }
+        Ok(())
     }
 
     pub fn builder_creation_impl(&self) -> Result<TokenStream, Error> {
+        let core = self.core_path();
         let StructInfo {
             vis,
             ref name,
@@ -56,7 +304,9 @@ impl<'a> StructInfo<'a> {
             ..
         } = *self;
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let empties_tuple = type_tuple(self.included_fields().map(|_| empty_type()));
+        let empties_tuple = type_tuple(self.included_fields().map(|f| self.unset_field_type(f)));
+        let empties = self.included_fields().map(|f| self.unset_field_value(f));
+        let empties = quote!(( #( #empties, )* ));
         let mut all_fields_param_type: syn::TypeParam =
             syn::Ident::new("TypedBuilderFields", proc_macro2::Span::call_site()).into();
         let all_fields_param = syn::GenericParam::Type(all_fields_param_type.clone());
@@ -69,20 +319,10 @@ impl<'a> StructInfo<'a> {
         let generics_with_empty = modify_types_generics_hack(&ty_generics, |args| {
             args.push(syn::GenericArgument::Type(empties_tuple.clone().into()));
         });
-        let phantom_generics = self.generics.params.iter().filter_map(|param| match param {
-            syn::GenericParam::Lifetime(lifetime) => {
-                let lifetime = &lifetime.lifetime;
-                Some(quote!(&#lifetime ()))
-            }
-            syn::GenericParam::Type(ty) => {
-                let ty = &ty.ident;
-                Some(ty.to_token_stream())
-            }
-            syn::GenericParam::Const(_cnst) => None,
-        });
+        let phantom_type = self.phantom_type();
 
-        let builder_method_name = self.builder_attr.builder_method.get_name().unwrap_or_else(|| quote!(builder));
-        let builder_method_visibility = first_visibility(&[
+        let builder_method_name = self.builder_method_name();
+        let builder_method_visibility = self.visibility(&[
             self.builder_attr.builder_method.vis.as_ref(),
             self.builder_attr.builder_type.vis.as_ref(),
             Some(vis),
@@ -92,20 +332,24 @@ impl<'a> StructInfo<'a> {
                 "
                 Create a builder for building `{name}`.
                 On the builder, call {setters} to set the values of the fields.
-                Finally, call `.build()` to create the instance of `{name}`.
+                Finally, call `.{build_method_name}()` to create the instance of `{name}`.
                 ",
-                name = self.name,
+                name = match self.variant {
+                    Some(variant) => format!("{}::{}", self.name, variant),
+                    None => self.name.to_string(),
+                },
+                build_method_name = self.build_method_name(),
                 setters = {
                     let mut result = String::new();
                     let mut is_first = true;
-                    for field in self.included_fields() {
+                    for field in self.included_fields().filter(|f| f.has_setter()) {
                         use std::fmt::Write;
                         if is_first {
                             is_first = false;
                         } else {
                             write!(&mut result, ", ").unwrap();
                         }
-                        write!(&mut result, "`.{}(...)`", field.name).unwrap();
+                        write!(&mut result, "`.{}(...)`", field.setter_method_name()).unwrap();
                         if field.builder_attr.default.is_some() {
                             write!(&mut result, "(optional)").unwrap();
                         }
@@ -115,103 +359,508 @@ impl<'a> StructInfo<'a> {
             )
         });
 
-        let builder_type_visibility = first_visibility(&[self.builder_attr.builder_type.vis.as_ref(), Some(vis)]);
-        let builder_type_doc = if self.builder_attr.doc {
-            self.builder_attr.builder_type.get_doc_or(|| {
+        let builder_type_visibility = self.builder_type_visibility();
+        let builder_type_doc = if self.builder_attr.doc || self.builder_attr.doc_examples {
+            let mut doc = self.builder_attr.builder_type.get_doc_or(|| {
                 format!(
-                    "Builder for [`{name}`] instances.\n\nSee [`{name}::builder()`] for more info.",
+                    "Builder for [`{name}`] instances.\n\nSee [`{name}::{builder_method_name}()`] for more info.",
                     name = name
                 )
-            })
+            });
+            if self.builder_attr.doc_examples {
+                let example = self.doc_example();
+                doc.extend(quote!(#[doc = #example]));
+            }
+            doc
         } else {
             quote!(#[doc(hidden)])
         };
 
+        // All the fields have defaults with `test_builder`, so the initial state can already be built.
+        let test_builder_method = if self.builder_attr.test_builder.is_some() {
+            let doc = format!(
+                "Create a builder for building `{name}` in tests, which can be built right away with the \
+                 defaults of all the fields - call the setters only for the fields the test cares about.",
+                name = name
+            );
+            quote! {
+                #[doc = #doc]
+                #[allow(dead_code)]
+                #builder_method_visibility fn test_builder() -> #builder_name #generics_with_empty {
+                    Self::#builder_method_name()
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let builder_default_impl = if self.builder_attr.builder_impl_default {
+            quote! {
+                impl #impl_generics #core::default::Default for #builder_name #generics_with_empty #where_clause {
+                    fn default() -> Self {
+                        #builder_name {
+                            fields: #empties,
+                            phantom: #core::default::Default::default(),
+                        }
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let into_builder_impl = if self.builder_attr.into_builder {
+            let filled_tuple = type_tuple(self.included_fields().map(|f| {
+                if f.is_mutable() {
+                    self.unset_field_type(f)
+                } else {
+                    f.tuplized_type_ty_param()
+                }
+            }));
+            let generics_with_filled = modify_types_generics_hack(&ty_generics, |args| {
+                args.push(syn::GenericArgument::Type(filled_tuple.clone().into()));
+            });
+            // Skipped fields are not stored in the builder - they get recomputed on build.
+            let patterns = self.included_fields().map(|f| match &f.member {
+                syn::Member::Named(name) => quote!(#name),
+                member @ syn::Member::Unnamed(_) => {
+                    let name = &f.name;
+                    quote!(#member: #name)
+                }
+            });
+            let values = self.included_fields().map(|f| {
+                let name = &f.name;
+                if f.is_mutable() {
+                    quote!(#core::option::Option::Some(#name))
+                } else {
+                    quote!((#name,))
+                }
+            });
+            let doc = format!(
+                "Create a builder with all the fields of this `{}` already set, so that some of them can be overridden before building it again.",
+                name
+            );
+            // The fields can't be moved out of a type that implements `Drop`. Rather than failing on
+            // that inside the generated method, the struct is checked with an impl that would conflict
+            // with a blanket impl for all `Drop` types, so the error names the actual problem.
+            let drop_check_trait = syn::Ident::new(
+                &format!("{}_into_builder_requires_that_it_does_not_implement_Drop", name),
+                name.span(),
+            );
+            let drop_check_impl = quote_spanned! {name.span()=>
+                impl #impl_generics #drop_check_trait for #name #ty_generics #where_clause {}
+            };
+            quote! {
+                const _: () = {
+                    #[allow(non_camel_case_types)]
+                    trait #drop_check_trait {}
+                    #[allow(drop_bounds)]
+                    impl<T: #core::ops::Drop> #drop_check_trait for T {}
+                    #drop_check_impl
+                };
+
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #[doc = #doc]
+                    #[allow(dead_code, deprecated, clippy::default_trait_access)]
+                    #builder_method_visibility fn into_builder(self) -> #builder_name #generics_with_filled {
+                        let #name { #( #patterns, )* .. } = self;
+                        #builder_name {
+                            fields: ( #( #values, )* ),
+                            phantom: #core::default::Default::default(),
+                        }
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let field_metadata_impl = if self.builder_attr.field_metadata {
+            let required_fields = self
+                .included_fields()
+                .filter(|f| f.is_required())
+                .map(|f| strip_raw_ident_prefix(f.name.to_string()));
+            quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// The names of the fields that must be set on the builder before it can be built.
+                    #[allow(dead_code)]
+                    #builder_method_visibility const REQUIRED_BUILDER_FIELDS: &'static [&'static str] = &[ #( #required_fields ),* ];
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let exposed_defaults = self.fields.iter().filter_map(|field| {
+            let const_name = self.exposed_default_name(field)?;
+            let ty = field.ty;
+            let default = field.builder_attr.default.as_ref()?;
+            let doc = format!(
+                "The default value of the `{}` field.",
+                strip_raw_ident_prefix(field.name.to_string())
+            );
+            Some(quote! {
+                #[doc = #doc]
+                #builder_method_visibility const #const_name: #ty = #default;
+            })
+        });
+        let exposed_defaults = exposed_defaults.collect::<Vec<_>>();
+        let expose_defaults_impl = if exposed_defaults.is_empty() {
+            quote!()
+        } else {
+            quote! {
+                #[allow(dead_code)]
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #( #exposed_defaults )*
+                }
+            }
+        };
+
+        let builder_type_attrs = self.builder_attr.builder_type.get_attrs();
+        let builder_method_attrs = self.builder_attr.builder_method.get_attrs();
+        let must_use = if self.builder_attr.no_must_use {
+            quote!()
+        } else {
+            quote!(#[must_use])
+        };
+
+        // Whether a field was set is part of the builder's type, so it's reported through a trait
+        // that is implemented for each of the types a field can have in the type-state.
+        let introspect_impl = if self.builder_attr.introspect {
+            let crate_path = self.crate_path();
+            let mut generics = self.generics.clone();
+            for field in self.included_fields() {
+                let mut generic_param: syn::TypeParam = field.generic_ident.clone().into();
+                generic_param.bounds.push(syn::parse_quote!(#crate_path::IsSet));
+                generics.params.push(generic_param.into());
+            }
+            let (impl_generics, _, _) = generics.split_for_impl();
+            let generics_with_states = modify_types_generics_hack(&ty_generics, |args| {
+                args.push(syn::GenericArgument::Type(
+                    type_tuple(self.included_fields().map(|f| f.type_ident())).into(),
+                ));
+            });
+            let count = self.included_fields().count();
+            let states = self.included_fields().enumerate().map(|(i, f)| {
+                let index = syn::Index::from(i);
+                let name = strip_raw_ident_prefix(f.name.to_string());
+                quote!((#name, #crate_path::IsSet::is_set(&self.fields.#index)))
+            });
+            quote! {
+                #[allow(dead_code, non_camel_case_types)]
+                impl #impl_generics #builder_name #generics_with_states #where_clause {
+                    /// The names of the fields that can be set on this builder, each with whether it
+                    /// was already set. Skipped fields are not included.
+                    #builder_type_visibility fn field_states(&self) -> [(&'static str, bool); #count] {
+                        [ #( #states ),* ]
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
         let (b_generics_impl, b_generics_ty, b_generics_where_extras_predicates) = b_generics.split_for_impl();
-        let mut b_generics_where: syn::WhereClause = syn::parse2(quote! {
-            where TypedBuilderFields: Clone
-        })?;
-        if let Some(predicates) = b_generics_where_extras_predicates {
-            b_generics_where.predicates.extend(predicates.predicates.clone());
-        }
+        // The traits implemented for the builder only require the values of the fields to implement
+        // them, so they are bound on `TypedBuilderFields` rather than on the struct's generics.
+        let where_fields_implement = |bound: TokenStream| -> Result<syn::WhereClause, Error> {
+            let mut where_clause: syn::WhereClause = syn::parse2(quote! {
+                where TypedBuilderFields: #bound
+            })?;
+            if let Some(predicates) = b_generics_where_extras_predicates {
+                where_clause.predicates.extend(predicates.predicates.clone());
+            }
+            Ok(where_clause)
+        };
+        let b_generics_where = where_fields_implement(quote!(Clone))?;
+
+        let extra_impl = if self.builder_attr.extra_impl.is_empty() {
+            quote!()
+        } else {
+            let mut items = self.builder_attr.extra_impl.clone();
+            if self.builder_attr.module.is_some() {
+                for item in &mut items {
+                    match item {
+                        syn::ImplItem::Const(syn::ImplItemConst { vis, .. })
+                        | syn::ImplItem::Fn(syn::ImplItemFn { vis, .. })
+                        | syn::ImplItem::Type(syn::ImplItemType { vis, .. }) => *vis = visibility_from_submodule(vis),
+                        _ => {}
+                    }
+                }
+            }
+            quote! {
+                #[allow(dead_code, non_camel_case_types)]
+                impl #b_generics_impl #builder_name #b_generics_ty #b_generics_where_extras_predicates {
+                    #( #items )*
+                }
+            }
+        };
+
+        // Associated consts are only evaluated when they are used, so the build method refers to this
+        // one to check each builder type that actually gets built.
+        let zero_cost_impl = if self.builder_attr.assert_zero_cost {
+            // The fields that are not set take no space, so the builder can only be larger than the
+            // values of all the fields when some type-state is kept at runtime - like the `Option` of a
+            // mutable setter.
+            let field_types = self.included_fields().map(|f| f.ty);
+            quote! {
+                #[allow(dead_code, non_camel_case_types)]
+                impl #b_generics_impl #builder_name #b_generics_ty #b_generics_where_extras_predicates {
+                    #[doc(hidden)]
+                    const __TYPED_BUILDER_ZERO_COST: () = {
+                        assert!(
+                            #core::mem::size_of::<#phantom_type>() == 0,
+                            "the builder's phantom data is not zero-sized",
+                        );
+                        assert!(
+                            #core::mem::size_of::<Self>() <= #core::mem::size_of::<( #( #field_types, )* )>(),
+                            "the builder is larger than the values of its fields",
+                        );
+                    };
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let merge_impl = if self.builder_attr.mergeable {
+            let merged = self.included_fields().enumerate().map(|(i, f)| {
+                let index = syn::Index::from(i);
+                if f.is_mutable() {
+                    quote!(#core::option::Option::or(self.fields.#index, other.fields.#index))
+                } else {
+                    quote!(self.fields.#index)
+                }
+            });
+            quote! {
+                #[allow(dead_code, non_camel_case_types)]
+                impl #impl_generics #builder_name #generics_with_empty #where_clause {
+                    /// Fills the fields that are not set in this builder with their values from `other`.
+                    /// Fields that are only changed by mutators keep their values from this builder.
+                    #[must_use]
+                    #builder_type_visibility fn merge(self, other: Self) -> Self {
+                        #builder_name {
+                            fields: ( #( #merged, )* ),
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let builder_debug_impl = if self.builder_attr.derive_debug {
+            let debug_where = where_fields_implement(quote!(#core::fmt::Debug))?;
+            let builder_name_str = builder_name.to_string();
+            quote! {
+                impl #b_generics_impl #core::fmt::Debug for #builder_name #b_generics_ty #debug_where {
+                    fn fmt(&self, f: &mut #core::fmt::Formatter<'_>) -> #core::fmt::Result {
+                        f.debug_struct(#builder_name_str).field("fields", &self.fields).finish()
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let builder_partial_eq_impl = if self.builder_attr.derive_partial_eq {
+            let partial_eq_where = where_fields_implement(quote!(#core::cmp::PartialEq))?;
+            quote! {
+                impl #b_generics_impl #core::cmp::PartialEq for #builder_name #b_generics_ty #partial_eq_where {
+                    fn eq(&self, other: &Self) -> bool {
+                        self.fields == other.fields
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let builder_eq_impl = if self.builder_attr.derive_eq {
+            let eq_where = where_fields_implement(quote!(#core::cmp::Eq))?;
+            quote! {
+                impl #b_generics_impl #core::cmp::Eq for #builder_name #b_generics_ty #eq_where {}
+            }
+        } else {
+            quote!()
+        };
+
+        let builder_hash_impl = if self.builder_attr.derive_hash {
+            let hash_where = where_fields_implement(quote!(#core::hash::Hash))?;
+            quote! {
+                impl #b_generics_impl #core::hash::Hash for #builder_name #b_generics_ty #hash_where {
+                    fn hash<__H: #core::hash::Hasher>(&self, state: &mut __H) {
+                        #core::hash::Hash::hash(&self.fields, state);
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
 
         Ok(quote! {
             impl #impl_generics #name #ty_generics #where_clause {
                 #builder_method_doc
+                #builder_method_attrs
                 #[allow(dead_code, clippy::default_trait_access)]
                 #builder_method_visibility fn #builder_method_name() -> #builder_name #generics_with_empty {
                     #builder_name {
-                        fields: #empties_tuple,
-                        phantom: ::core::default::Default::default(),
+                        fields: #empties,
+                        phantom: #core::default::Default::default(),
                     }
                 }
+
+                #test_builder_method
             }
 
-            #[must_use]
+            #must_use
             #builder_type_doc
+            #builder_type_attrs
             #[allow(dead_code, non_camel_case_types, non_snake_case)]
             #builder_type_visibility struct #builder_name #b_generics {
                 fields: #all_fields_param,
-                phantom: ::core::marker::PhantomData<(#( #phantom_generics ),*)>,
+                phantom: #phantom_type,
             }
 
+            #builder_default_impl
+
+            #into_builder_impl
+
+            #field_metadata_impl
+
+            #expose_defaults_impl
+
+            #introspect_impl
+
             impl #b_generics_impl Clone for #builder_name #b_generics_ty #b_generics_where {
                 #[allow(clippy::default_trait_access)]
                 fn clone(&self) -> Self {
                     Self {
                         fields: self.fields.clone(),
-                        phantom: ::core::marker::PhantomData,
+                        phantom: #core::marker::PhantomData,
                     }
                 }
             }
+
+            #extra_impl
+            #zero_cost_impl
+            #merge_impl
+            #builder_debug_impl
+            #builder_partial_eq_impl
+            #builder_eq_impl
+            #builder_hash_impl
         })
     }
 
-    // TODO: once the proc-macro crate limitation is lifted, make this an util trait of this
-    // crate. (See issue #109)
-    pub fn conversion_helper_impl(&self) -> TokenStream {
-        let trait_name = &self.conversion_helper_trait_name;
-        quote! {
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, non_snake_case)]
-            pub trait #trait_name<T> {
-                fn into_value<F: FnOnce() -> T>(self, default: F) -> T;
-            }
+    fn core_path(&self) -> TokenStream {
+        self.builder_attr
+            .crate_module
+            .as_ref()
+            .map_or_else(|| quote!(::core), |path| path.to_token_stream())
+    }
 
-            impl<T> #trait_name<T> for () {
-                fn into_value<F: FnOnce() -> T>(self, default: F) -> T {
-                    default()
-                }
-            }
+    fn crate_path(&self) -> TokenStream {
+        self.builder_attr
+            .crate_path
+            .as_ref()
+            .map_or_else(|| quote!(::typed_builder), |path| path.to_token_stream())
+    }
 
-            impl<T> #trait_name<T> for (T,) {
-                fn into_value<F: FnOnce() -> T>(self, _: F) -> T {
-                    self.0
-                }
+    /// The name of the associated const that holds the default of a field with
+    /// `#[builder(expose_defaults)]`. Only literal defaults are known to be const expressions, so
+    /// other defaults stay inline in the build method.
+    fn exposed_default_name(&self, field: &FieldInfo) -> Option<syn::Ident> {
+        if !self.builder_attr.expose_defaults {
+            return None;
+        }
+        let is_literal = match field.builder_attr.default.as_ref()? {
+            syn::Expr::Lit(_) => true,
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => matches!(**expr, syn::Expr::Lit(_)),
+            _ => false,
+        };
+        is_literal.then(|| {
+            let name = strip_raw_ident_prefix(field.name.to_string()).to_uppercase();
+            syn::Ident::new(&format!("DEFAULT_{}", name), proc_macro2::Span::call_site())
+        })
+    }
+
+    /// The exposed defaults are named after the fields in upper case, so fields that only differ in
+    /// case (e.g. `foo` and `Foo`) would get the same const.
+    fn check_exposed_default_names(&self) -> Result<(), Error> {
+        let mut names: Vec<(syn::Ident, &syn::Ident)> = Vec::new();
+        for field in &self.fields {
+            let Some(const_name) = self.exposed_default_name(field) else {
+                continue;
+            };
+            if let Some((_, other)) = names.iter().find(|(name, _)| *name == const_name) {
+                return Err(Error::new_spanned(
+                    &field.name,
+                    format!(
+                        "the exposed default of `{}` would be named `{}`, like that of `{}`",
+                        strip_raw_ident_prefix(field.name.to_string()),
+                        const_name,
+                        strip_raw_ident_prefix(other.to_string()),
+                    ),
+                ));
             }
+            names.push((const_name, &field.name));
         }
+        Ok(())
     }
 
-    pub fn field_impl(&self, field: &FieldInfo) -> Result<TokenStream, Error> {
-        let StructInfo { ref builder_name, .. } = *self;
+    /// The type of a field in the builder's type-state before it is set. This is `()`, except for
+    /// fields with a mutable setter, which are stored as an `Option` right from the start.
+    ///
+    /// Fields that are only changed by mutators are always set, starting with their initial value.
+    fn unset_field_type(&self, field: &FieldInfo) -> syn::Type {
+        if field.is_mutable() {
+            let core = self.core_path();
+            let ty = field.ty;
+            syn::parse2(quote!(#core::option::Option<#ty>)).unwrap()
+        } else if field.builder_attr.via_mutators.is_some() {
+            field.tuplized_type_ty_param()
+        } else {
+            empty_type()
+        }
+    }
 
-        let descructuring = self.included_fields().map(|f| {
-            if f.ordinal == field.ordinal {
-                quote!(_)
-            } else {
-                let name = f.name;
-                name.to_token_stream()
+    fn unset_field_value(&self, field: &FieldInfo) -> TokenStream {
+        if field.is_mutable() {
+            let core = self.core_path();
+            quote!(#core::option::Option::None)
+        } else if let Some(init) = &field.builder_attr.via_mutators {
+            quote!((#init,))
+        } else {
+            quote!(())
+        }
+    }
+
+    fn phantom_type(&self) -> TokenStream {
+        let core = self.core_path();
+        let phantom_generics = self.generics.params.iter().filter_map(|param| match param {
+            syn::GenericParam::Lifetime(lifetime) => {
+                let lifetime = &lifetime.lifetime;
+                Some(quote!(&#lifetime ()))
             }
+            // Only the last element of a tuple may be unsized, so `?Sized` parameters get their own
+            // `PhantomData`.
+            syn::GenericParam::Type(ty) => {
+                let ty = &ty.ident;
+                Some(quote!(#core::marker::PhantomData<#ty>))
+            }
+            // Unused const parameters are allowed, and they are not types, so they can't go in `PhantomData`.
+            syn::GenericParam::Const(_cnst) => None,
         });
-        let reconstructing = self.included_fields().map(|f| f.name);
+        quote!(#core::marker::PhantomData<(#( #phantom_generics ),*)>)
+    }
 
-        let &FieldInfo {
-            name: field_name,
-            ty: field_type,
-            ..
-        } = field;
-        let mut ty_generics: Vec<syn::GenericArgument> = self
-            .generics
+    fn generic_arguments(&self) -> Vec<syn::GenericArgument> {
+        self.generics
             .params
             .iter()
             .map(|generic_param| match generic_param {
@@ -225,14 +874,48 @@ impl<'a> StructInfo<'a> {
                     syn::parse2(ident).unwrap()
                 }
             })
-            .collect();
+            .collect()
+    }
+
+    // NOTE: the setters can't be `const fn`, because they are generic over the type-state of the
+    // other fields, and stable Rust does not allow moving the fields out of a value of a generic
+    // type in a `const fn` (that requires the unstable `const_precise_live_drops`).
+    pub fn field_impl(&self, field: &FieldInfo) -> Result<TokenStream, Error> {
+        let core = self.core_path();
+        let StructInfo { ref builder_name, .. } = *self;
+
+        let descructuring = self
+            .included_fields()
+            .map(|f| {
+                if f.ordinal == field.ordinal {
+                    quote!(_)
+                } else {
+                    let name = &f.name;
+                    name.to_token_stream()
+                }
+            })
+            .collect::<Vec<_>>();
+        let reconstructing = self.included_fields().map(|f| &f.name).collect::<Vec<_>>();
+
+        let &FieldInfo {
+            name: ref field_name,
+            ty: field_type,
+            ..
+        } = field;
+        let mut ty_generics = self.generic_arguments();
         let mut target_generics_tuple = empty_type_tuple();
         let mut ty_generics_tuple = empty_type_tuple();
+        // With `phased`, the setters of the optional fields are only available once all the required
+        // fields are set.
+        let requires_required_fields = self.builder_attr.phased && !field.is_required();
         let generics = {
             let mut generics = self.generics.clone();
             for f in self.included_fields() {
                 if f.ordinal == field.ordinal {
-                    ty_generics_tuple.elems.push_value(empty_type());
+                    ty_generics_tuple.elems.push_value(self.unset_field_type(f));
+                    target_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
+                } else if requires_required_fields && f.is_required() {
+                    ty_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
                     target_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
                 } else {
                     generics.params.push(f.generic_ty_param());
@@ -249,20 +932,116 @@ impl<'a> StructInfo<'a> {
         target_generics.push(syn::GenericArgument::Type(target_generics_tuple.into()));
         ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
         let (impl_generics, _, where_clause) = generics.split_for_impl();
-        let doc = field.builder_attr.setter.doc.as_ref().map(|doc| quote!(#[doc = #doc]));
+        let doc = if let Some(doc) = &field.builder_attr.setter.doc {
+            quote!(#[doc = #doc])
+        } else {
+            let doc_attrs = &field.doc_attrs;
+            quote!(#( #doc_attrs )*)
+        };
         let deprecated = &field.builder_attr.deprecated;
 
-        // NOTE: both auto_into and strip_option affect `arg_type` and `arg_expr`, but the order of
-        // nesting is different so we have to do this little dance.
-        let arg_type = if field.builder_attr.setter.strip_option.is_some() && field.builder_attr.setter.transform.is_none() {
-            field
-                .type_from_inside_option()
-                .ok_or_else(|| Error::new_spanned(field_type, "can't `strip_option` - field is not `Option<...>`"))?
+        // NOTE: both auto_into and strip_option (or auto_box and the like) affect `arg_type` and `arg_expr`, but
+        // the order of nesting is different so we have to do this little dance.
+        let arg_type = if let (Some(strip_option_span), None) =
+            (field.builder_attr.setter.strip_option, &field.builder_attr.setter.transform)
+        {
+            field.type_from_inside_option().ok_or_else(|| {
+                let mut error = Error::new(strip_option_span, "can't `strip_option` - field is not `Option<...>`");
+                error.combine(Error::new_spanned(field_type, "field type declared here"));
+                error
+            })?
+        } else if let Some(wrap_with) = &field.builder_attr.setter.wrap_with {
+            field.type_for_wrap_with().ok_or_else(|| {
+                let mut error = Error::new_spanned(
+                    wrap_with,
+                    "can't tell the argument type of `wrap_with` - give it with `into = ...`",
+                );
+                error.combine(Error::new_spanned(field_type, "field type declared here"));
+                error
+            })?
+        } else if let Some((key, pointer, span)) = field.auto_pointer() {
+            extract_wrapped_type(field_type, pointer).ok_or_else(|| {
+                let mut error = Error::new(span, format!("can't `{}` - field is not `{}<...>`", key, pointer));
+                error.combine(Error::new_spanned(field_type, "field type declared here"));
+                error
+            })?
         } else {
             field_type
         };
-        let (arg_type, arg_expr) = if field.builder_attr.setter.auto_into.is_some() {
-            (quote!(impl ::core::convert::Into<#arg_type>), quote!(#field_name.into()))
+        // A `from_ref` setter clones the argument, so its type (e.g. a generic parameter) must be `Clone`.
+        let setter_where_clause = if field.builder_attr.setter.from_ref.is_some() {
+            quote!(where #arg_type: #core::clone::Clone)
+        } else {
+            quote!()
+        };
+        // A trait object can't be passed by value, so the setter of a `Box<dyn Trait>` field (or an
+        // `Arc`/`Rc` one) takes any `impl Trait` instead. Like in the field's type, the trait object
+        // is `'static` unless it has a lifetime bound.
+        let pointee_impl_trait = match (field.auto_pointer(), arg_type) {
+            (Some((key, _, span)), syn::Type::TraitObject(trait_object)) => {
+                let setter = &field.builder_attr.setter;
+                if setter.auto_into.is_some() || setter.into_type.is_some() || setter.try_into.is_some() {
+                    return Err(Error::new(
+                        span,
+                        format!("`{}` of a trait object can't be combined with `into` or `try_into`", key),
+                    ));
+                }
+                let bounds = &trait_object.bounds;
+                let has_lifetime = bounds.iter().any(|bound| matches!(bound, syn::TypeParamBound::Lifetime(_)));
+                Some(if has_lifetime {
+                    quote!(impl #bounds)
+                } else {
+                    quote!(impl #bounds + 'static)
+                })
+            }
+            _ => None,
+        };
+        // A `try_into` setter is generic over the argument's type, since its return type has to name
+        // the conversion's error type.
+        let try_into_type = self.unused_generic_ident("__Value");
+        let try_into_bound = quote!(#core::convert::TryInto<#arg_type>);
+        let (arg_type, arg_expr) = if let Some(pointee_impl_trait) = &pointee_impl_trait {
+            (pointee_impl_trait.clone(), field_name.to_token_stream())
+        } else if field.builder_attr.setter.try_into.is_some() {
+            (
+                try_into_type.to_token_stream(),
+                quote!(#core::convert::TryInto::<#arg_type>::try_into(#field_name)?),
+            )
+        } else if let Some(into_type) = &field.builder_attr.setter.into_type {
+            (
+                quote!(impl #core::convert::Into<#into_type>),
+                quote!(#core::convert::Into::<#arg_type>::into(#core::convert::Into::<#into_type>::into(#field_name))),
+            )
+        } else if field.builder_attr.setter.auto_into.is_some() {
+            (quote!(impl #core::convert::Into<#arg_type>), quote!(#field_name.into()))
+        } else if field.builder_attr.setter.from_ref.is_some() {
+            (quote!(&#arg_type), quote!(#core::clone::Clone::clone(#field_name)))
+        } else if field.builder_attr.setter.into_iter.is_some() {
+            let item_type = quote!(<#arg_type as #core::iter::IntoIterator>::Item);
+            (
+                quote!(impl #core::iter::IntoIterator<Item = impl #core::convert::Into<#item_type>>),
+                quote!(#core::iter::Iterator::collect::<#arg_type>(
+                    #core::iter::Iterator::map(#core::iter::IntoIterator::into_iter(#field_name), #core::convert::Into::into)
+                )),
+            )
+        } else if let Some(nested_span) = field.builder_attr.setter.nested {
+            let builder_type = match &field.builder_attr.setter.nested_builder {
+                Some(builder_type) => builder_type.clone(),
+                None => default_builder_type(arg_type).ok_or_else(|| {
+                    Error::new(
+                        nested_span,
+                        "can't tell the builder type of the field - give it with `nested = ...`",
+                    )
+                })?,
+            };
+            let new_builder = match &field.builder_attr.setter.nested_builder_method {
+                Some(builder_method) => quote!(#builder_method()),
+                None => quote!(<#arg_type>::builder()),
+            };
+            (
+                quote!(impl #core::ops::FnOnce(#builder_type) -> #arg_type),
+                quote!(#field_name(#new_builder)),
+            )
         } else {
             (arg_type.to_token_stream(), field_name.to_token_stream())
         };
@@ -272,9 +1051,25 @@ impl<'a> StructInfo<'a> {
         } else if let Some(transform) = &field.builder_attr.setter.transform {
             let params = transform.params.iter().map(|(pat, ty)| quote!(#pat: #ty));
             let body = &transform.body;
-            (quote!(#(#params),*), quote!({ #body }))
+            // Binding with an explicit type makes a type mismatch point at the transform's body.
+            (
+                quote!(#(#params),*),
+                quote!({ let transformed: #field_type = #body; transformed }),
+            )
         } else if field.builder_attr.setter.strip_option.is_some() {
             (quote!(#field_name: #arg_type), quote!(Some(#arg_expr)))
+        } else if let Some(wrap_with) = &field.builder_attr.setter.wrap_with {
+            (quote!(#field_name: #arg_type), quote!(#wrap_with(#arg_expr)))
+        } else if field.auto_pointer().is_some() {
+            // Going through the field's own type works with whatever path it uses for the pointer.
+            // For trait objects the pointer is created with the argument's type and then coerced.
+            let arg_expr = match (&pointee_impl_trait, path_without_generic_arguments(field_type)) {
+                (Some(_), Some(pointer_path)) => {
+                    quote!({ let pointer: #field_type = #pointer_path::new(#arg_expr); pointer })
+                }
+                _ => quote!(<#field_type>::new(#arg_expr)),
+            };
+            (quote!(#field_name: #arg_type), arg_expr)
         } else {
             (quote!(#field_name: #arg_type), arg_expr)
         };
@@ -287,37 +1082,143 @@ impl<'a> StructInfo<'a> {
             ),
             proc_macro2::Span::call_site(),
         );
-        let repeated_fields_error_message = format!("Repeated field {}", field_name);
+        let repeated_fields_error_message = format!("Repeated field {}", strip_raw_ident_prefix(field_name.to_string()));
 
         let method_name = field.setter_method_name();
+        // Lets the docs be searched by the field's name when the setter is named differently.
+        let field_name_str = strip_raw_ident_prefix(field_name.to_string());
+        let doc_alias = if strip_raw_ident_prefix(method_name.to_string()) == field_name_str {
+            quote!()
+        } else {
+            quote!(#[doc(alias = #field_name_str)])
+        };
+        let visibility = self.setter_visibility(field);
+        let inline = self.inline_attr();
 
+        // Returns the setter's generics, return type and returned value, which are wrapped in a `Result`
+        // for `try_into` setters.
+        let setter_signature = |return_type: TokenStream, value: TokenStream| {
+            if field.builder_attr.setter.try_into.is_some() {
+                (
+                    quote!(<#try_into_type: #try_into_bound>),
+                    quote!(#core::result::Result<#return_type, <#try_into_type as #try_into_bound>::Error>),
+                    quote!(#core::result::Result::Ok(#value)),
+                )
+            } else {
+                (quote!(), return_type, value)
+            }
+        };
+
+        // Mutable setters don't change the type-state - they just replace the `Option` the field is
+        // stored in, so they can be called any number of times.
+        if field.is_mutable() {
+            let index = syn::Index::from(
+                self.included_fields()
+                    .position(|f| f.ordinal == field.ordinal)
+                    .expect("field_impl called for a skipped field"),
+            );
+            let (method_generics, return_type, return_value) = setter_signature(quote!(&mut Self), quote!(self));
+            return Ok(quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::too_many_arguments, clippy::type_complexity)]
+                impl #impl_generics #builder_name < #( #ty_generics ),* > #where_clause {
+                    #deprecated
+                    #doc
+                    #doc_alias
+                    #inline
+                    #visibility fn #method_name #method_generics (&mut self, #param_list) -> #return_type #setter_where_clause {
+                        self.fields.#index = #core::option::Option::Some(#arg_expr);
+                        #return_value
+                    }
+                }
+            });
+        }
+
+        let each_setter = if let Some(each_name) = &field.builder_attr.setter.each {
+            let item_type = field
+                .type_from_inside_vec()
+                .ok_or_else(|| Error::new_spanned(each_name, "can't use `each` - field is not `Vec<...>`"))?;
+            let initial_value = field
+                .builder_attr
+                .default
+                .as_ref()
+                .map_or_else(|| quote!(#core::default::Default::default()), |default| quote!(#default));
+            // The parameter can't be named after the method, since that may also be the name of the
+            // field or of something the default refers to, so it's hygienic instead.
+            let item = syn::Ident::new("item", proc_macro2::Span::mixed_site());
+            quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
+                impl #impl_generics #builder_name < #( #ty_generics ),* > #where_clause {
+                    #deprecated
+                    #inline
+                    #visibility fn #each_name (self, #item: impl #core::convert::Into<#item_type>) -> #builder_name <#( #target_generics ),*> {
+                        let mut #field_name: #field_type = #initial_value;
+                        #field_name.push(#item.into());
+                        let #field_name = (#field_name,);
+                        let ( #(#descructuring,)* ) = self.fields;
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
+                impl #impl_generics #builder_name < #( #target_generics ),* > #where_clause {
+                    #deprecated
+                    #inline
+                    #visibility fn #each_name (self, #item: impl #core::convert::Into<#item_type>) -> Self {
+                        let ( #(#reconstructing,)* ) = self.fields;
+                        let mut #field_name = #field_name;
+                        #field_name.0.push(#item.into());
+                        #builder_name {
+                            fields: ( #(#reconstructing,)* ),
+                            phantom: self.phantom,
+                        }
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        let (method_generics, return_type, return_value) = setter_signature(
+            quote!(#builder_name <#( #target_generics ),*>),
+            quote! {
+                #builder_name {
+                    fields: ( #(#reconstructing,)* ),
+                    phantom: self.phantom,
+                }
+            },
+        );
+        // Like in `required_field_impl`, the marker enum is put in an anonymous const.
         Ok(quote! {
-            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[allow(dead_code, non_camel_case_types, missing_docs, clippy::too_many_arguments, clippy::type_complexity)]
             impl #impl_generics #builder_name < #( #ty_generics ),* > #where_clause {
                 #deprecated
                 #doc
-                pub fn #method_name (self, #param_list) -> #builder_name <#( #target_generics ),*> {
+                #doc_alias
+                #inline
+                #visibility fn #method_name #method_generics (self, #param_list) -> #return_type #setter_where_clause {
                     let #field_name = (#arg_expr,);
                     let ( #(#descructuring,)* ) = self.fields;
-                    #builder_name {
-                        fields: ( #(#reconstructing,)* ),
-                        phantom: self.phantom,
-                    }
+                    #return_value
                 }
             }
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, non_snake_case)]
-            pub enum #repeated_fields_error_type_name {}
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, missing_docs)]
-            impl #impl_generics #builder_name < #( #target_generics ),* > #where_clause {
-                #[deprecated(
-                    note = #repeated_fields_error_message
-                )]
-                pub fn #method_name (self, _: #repeated_fields_error_type_name) -> #builder_name <#( #target_generics ),*> {
-                    self
+            const _: () = {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                pub enum #repeated_fields_error_type_name {}
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
+                impl #impl_generics #builder_name < #( #target_generics ),* > #where_clause {
+                    #[deprecated(
+                        note = #repeated_fields_error_message
+                    )]
+                    #visibility fn #method_name (self, _: #repeated_fields_error_type_name) -> Self {
+                        self
+                    }
                 }
-            }
+            };
+            #each_setter
         })
     }
 
@@ -327,22 +1228,7 @@ impl<'a> StructInfo<'a> {
         let FieldInfo {
             name: ref field_name, ..
         } = field;
-        let mut builder_generics: Vec<syn::GenericArgument> = self
-            .generics
-            .params
-            .iter()
-            .map(|generic_param| match generic_param {
-                syn::GenericParam::Type(type_param) => {
-                    let ident = type_param.ident.to_token_stream();
-                    syn::parse2(ident).unwrap()
-                }
-                syn::GenericParam::Lifetime(lifetime_def) => syn::GenericArgument::Lifetime(lifetime_def.lifetime.clone()),
-                syn::GenericParam::Const(const_param) => {
-                    let ident = const_param.ident.to_token_stream();
-                    syn::parse2(ident).unwrap()
-                }
-            })
-            .collect();
+        let mut builder_generics = self.generic_arguments();
         let mut builder_generics_tuple = empty_type_tuple();
         let generics = {
             let mut generics = self.generics.clone();
@@ -371,53 +1257,582 @@ impl<'a> StructInfo<'a> {
                     builder_generics_tuple.elems.push_value(f.type_ident());
                 }
 
-                builder_generics_tuple.elems.push_punct(Default::default());
+                builder_generics_tuple.elems.push_punct(Default::default());
+            }
+            generics
+        };
+
+        builder_generics.push(syn::GenericArgument::Type(builder_generics_tuple.into()));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let early_build_error_type_name = syn::Ident::new(
+            &format!(
+                "{}_Error_Missing_required_field_{}",
+                builder_name,
+                strip_raw_ident_prefix(field_name.to_string())
+            ),
+            proc_macro2::Span::call_site(),
+        );
+        let early_build_error_message = format!("Missing required field {}", strip_raw_ident_prefix(field_name.to_string()));
+
+        let build_method_name = self.build_method_name();
+        let build_method_visibility = self.build_method_visibility();
+
+        // The marker enum is put in an anonymous const, so that its name can't collide with the
+        // items generated for other builders in the same module.
+        quote! {
+            const _: () = {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                pub enum #early_build_error_type_name {}
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::panic, clippy::type_complexity)]
+                impl #impl_generics #builder_name < #( #builder_generics ),* > #where_clause {
+                    #[deprecated(
+                        note = #early_build_error_message
+                    )]
+                    #build_method_visibility fn #build_method_name(self, _: #early_build_error_type_name) -> ! {
+                        panic!()
+                    }
+                }
+            };
+        }
+    }
+
+    /// The `get_x` method of `#[builder(getters)]`, which is only available once `x` is set - except
+    /// for fields with a mutable setter, which are stored as an `Option` and can always be read.
+    pub fn getter_impl(&self, field: &FieldInfo) -> TokenStream {
+        let core = self.core_path();
+        let StructInfo { ref builder_name, .. } = *self;
+        let visibility = self.builder_type_visibility();
+
+        let mut ty_generics = self.generic_arguments();
+        let mut ty_generics_tuple = empty_type_tuple();
+        let generics = {
+            let mut generics = self.generics.clone();
+            for f in self.included_fields() {
+                if f.ordinal == field.ordinal && f.is_mutable() {
+                    ty_generics_tuple.elems.push_value(self.unset_field_type(f));
+                } else if f.ordinal == field.ordinal {
+                    ty_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
+                } else {
+                    generics.params.push(f.generic_ty_param());
+                    ty_generics_tuple.elems.push_value(f.type_ident());
+                }
+                ty_generics_tuple.elems.push_punct(Default::default());
+            }
+            generics
+        };
+        ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let index = syn::Index::from(
+            self.included_fields()
+                .position(|f| f.ordinal == field.ordinal)
+                .expect("getter_impl called for a skipped field"),
+        );
+        let getter_name = syn::Ident::new(
+            &format!("get_{}", strip_raw_ident_prefix(field.name.to_string())),
+            proc_macro2::Span::call_site(),
+        );
+        let field_type = field.ty;
+        let (return_type, value) = if field.is_mutable() {
+            (
+                quote!(#core::option::Option<&#field_type>),
+                quote!(#core::option::Option::as_ref(&self.fields.#index)),
+            )
+        } else {
+            (quote!(&#field_type), quote!(&self.fields.#index.0))
+        };
+        let doc = format!("The value `{}` was set to.", strip_raw_ident_prefix(field.name.to_string()));
+
+        quote! {
+            #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
+            impl #impl_generics #builder_name < #( #ty_generics ),* > #where_clause {
+                #[doc = #doc]
+                #visibility fn #getter_name(&self) -> #return_type {
+                    #value
+                }
+            }
+        }
+    }
+
+    pub fn mutator_impl(&self, mutator: &Mutator) -> Result<TokenStream, Error> {
+        let core = self.core_path();
+        let StructInfo { ref builder_name, .. } = *self;
+        let visibility = self.builder_type_visibility();
+
+        for accessed_field in &mutator.accessed_fields {
+            if !self.included_fields().any(|f| f.name == *accessed_field) {
+                return Err(Error::new_spanned(
+                    accessed_field,
+                    format!(
+                        "mutators can only access fields that have setters, and `{}` does not",
+                        accessed_field
+                    ),
+                ));
+            }
+            if self.included_fields().any(|f| f.name == *accessed_field && f.is_mutable()) {
+                return Err(Error::new_spanned(
+                    accessed_field,
+                    format!("mutators cannot access `{}`, since it has a mutable setter", accessed_field),
+                ));
+            }
+        }
+
+        let mut ty_generics = self.generic_arguments();
+        let mut ty_generics_tuple = empty_type_tuple();
+        let generics = {
+            let mut generics = self.generics.clone();
+            for f in self.included_fields() {
+                if mutator.accesses(&f.name) {
+                    ty_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
+                } else {
+                    generics.params.push(f.generic_ty_param());
+                    ty_generics_tuple.elems.push_value(f.type_ident());
+                }
+                ty_generics_tuple.elems.push_punct(Default::default());
+            }
+            generics
+        };
+        ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let fun = &mutator.fun;
+        let fn_name = &fun.sig.ident;
+        let (fn_generics, _, fn_where_clause) = fun.sig.generics.split_for_impl();
+        let fn_docs = fun.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+        // The arguments are renamed so they can't be shadowed by the destructured fields.
+        let (fn_params, fn_args): (Vec<_>, Vec<_>) = fun
+            .sig
+            .inputs
+            .iter()
+            .skip(1)
+            .enumerate()
+            .map(|(i, input)| {
+                let arg = syn::Ident::new(&format!("__arg{}", i), proc_macro2::Span::call_site());
+                match input {
+                    syn::FnArg::Typed(pat_type) => {
+                        let ty = &pat_type.ty;
+                        Ok((quote!(#arg: #ty), arg))
+                    }
+                    syn::FnArg::Receiver(receiver) => Err(Error::new_spanned(receiver, "unexpected receiver")),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+
+        let mutator_generics = self.generics;
+        let (mutator_impl_generics, mutator_ty_generics, mutator_where_clause) = self.generics.split_for_impl();
+        let phantom_type = self.phantom_type();
+        let phantom_field = self.ident_not_clashing_with_fields("__phantom");
+        let mutator_var = self.ident_not_clashing_with_fields("__mutator");
+        let mutator_fields = self.included_fields().filter(|f| mutator.accesses(&f.name)).map(|f| {
+            let FieldInfo { name, ty, .. } = f;
+            quote!(#name: #ty,)
+        });
+        let mutator_fields_init = self.included_fields().filter(|f| mutator.accesses(&f.name)).map(|f| {
+            let name = &f.name;
+            quote!(#name: #name.0,)
+        });
+        let descructuring = self.included_fields().map(|f| &f.name);
+        let reconstructing = self.included_fields().map(|f| {
+            let name = &f.name;
+            if mutator.accesses(name) {
+                quote!((#mutator_var.#name,))
+            } else {
+                name.to_token_stream()
+            }
+        });
+
+        Ok(quote! {
+            #[allow(dead_code, non_camel_case_types, missing_docs, clippy::too_many_arguments, clippy::type_complexity)]
+            impl #impl_generics #builder_name < #( #ty_generics ),* > #where_clause {
+                #( #fn_docs )*
+                #visibility fn #fn_name #fn_generics (self, #( #fn_params ),*) -> Self #fn_where_clause {
+                    struct TypedBuilderFieldMutator #mutator_generics #mutator_where_clause {
+                        #( #mutator_fields )*
+                        #phantom_field: #phantom_type,
+                    }
+                    impl #mutator_impl_generics TypedBuilderFieldMutator #mutator_ty_generics #mutator_where_clause {
+                        #fun
+                    }
+
+                    let ( #(#descructuring,)* ) = self.fields;
+                    let mut #mutator_var: TypedBuilderFieldMutator #mutator_ty_generics = TypedBuilderFieldMutator {
+                        #( #mutator_fields_init )*
+                        #phantom_field: #core::marker::PhantomData,
+                    };
+                    #mutator_var.#fn_name(#( #fn_args ),*);
+                    #builder_name {
+                        fields: ( #(#reconstructing,)* ),
+                        phantom: self.phantom,
+                    }
+                }
+            }
+        })
+    }
+
+    fn inline_attr(&self) -> TokenStream {
+        if self.builder_attr.inline {
+            quote!(#[inline])
+        } else {
+            quote!()
+        }
+    }
+
+    fn builder_method_name(&self) -> TokenStream {
+        self.builder_attr
+            .builder_method
+            .get_name()
+            .unwrap_or_else(|| match self.variant {
+                Some(variant) => {
+                    let name = to_snake_case(&strip_raw_ident_prefix(variant.to_string()));
+                    syn::Ident::new(&format!("{}_builder", name), proc_macro2::Span::call_site()).to_token_stream()
+                }
+                None => quote!(builder),
+            })
+    }
+
+    /// The path used for constructing the built value - the struct, or the variant of the enum.
+    fn constructed_path(&self) -> TokenStream {
+        let name = self.name;
+        match self.variant {
+            Some(variant) => quote!(#name::#variant),
+            None => quote!(#name),
+        }
+    }
+
+    /// A usage example for the docs of the builder type, which calls the setters of all the fields.
+    /// It is marked `ignore` since the doctest would not know which paths to import.
+    fn doc_example(&self) -> String {
+        use std::fmt::Write;
+        let mut example = format!(
+            "\n# Example\n\n```ignore\nlet {} = {}::{}()\n",
+            to_snake_case(&strip_raw_ident_prefix(self.name.to_string())),
+            self.name,
+            self.builder_method_name(),
+        );
+        for field in self.included_fields().filter(|f| f.has_setter()) {
+            write!(&mut example, "    .{}(...)", field.setter_method_name()).unwrap();
+            if field.builder_attr.default.is_some() {
+                write!(&mut example, " // optional").unwrap();
+            }
+            writeln!(&mut example).unwrap();
+        }
+        writeln!(&mut example, "    .{}();\n```", self.build_method_name()).unwrap();
+        example
+    }
+
+    fn build_method_name(&self) -> TokenStream {
+        self.builder_attr.build_method.common.get_name().unwrap_or(quote!(build))
+    }
+
+    /// The first of the given visibilities. When the builder is generated in a `module`, it is
+    /// adjusted to reach as far from that module as it would from the struct's module.
+    fn visibility(&self, visibilities: &[Option<&syn::Visibility>]) -> TokenStream {
+        if self.builder_attr.module.is_some() {
+            let vis = visibilities
+                .iter()
+                .flatten()
+                .next()
+                .expect("need at least one visibility in the list");
+            visibility_from_submodule(vis).to_token_stream()
+        } else {
+            first_visibility(visibilities)
+        }
+    }
+
+    /// Puts the generated items in the `module`, if one was given. The module itself has the
+    /// visibility of the builder type, so that the builder can be named wherever it is visible.
+    pub fn builder_module_impl(&self, items: TokenStream) -> TokenStream {
+        let Some(module) = &self.builder_attr.module else {
+            return items;
+        };
+        let vis = first_visibility(&[self.builder_attr.builder_type.vis.as_ref(), Some(self.vis)]);
+        let doc = if self.builder_attr.doc || self.builder_attr.doc_examples {
+            let doc = format!("The builder of [`{}`].", self.name);
+            quote!(#[doc = #doc])
+        } else {
+            quote!(#[doc(hidden)])
+        };
+        let cfg = self.builder_attr.cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+        quote! {
+            #doc
+            #cfg
+            #vis mod #module {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #items
             }
-            generics
+        }
+    }
+
+    fn builder_type_visibility(&self) -> TokenStream {
+        self.visibility(&[self.builder_attr.builder_type.vis.as_ref(), Some(self.vis)])
+    }
+
+    /// The methods on the builder are no more visible than the builder type itself, unless their
+    /// visibility is set explicitly.
+    fn build_method_visibility(&self) -> TokenStream {
+        self.visibility(&[
+            self.builder_attr.build_method.common.vis.as_ref(),
+            self.builder_attr.builder_type.vis.as_ref(),
+            Some(self.vis),
+        ])
+    }
+
+    fn setter_visibility(&self, field: &FieldInfo) -> TokenStream {
+        self.visibility(&[
+            field.builder_attr.setter.vis.as_ref(),
+            self.builder_attr.builder_type.vis.as_ref(),
+            Some(self.vis),
+        ])
+    }
+
+    fn build_error_type_name(&self) -> syn::Ident {
+        self.builder_attr
+            .build_method
+            .error
+            .clone()
+            .unwrap_or_else(|| syn::Ident::new(&format!("{}Error", self.builder_name), proc_macro2::Span::call_site()))
+    }
+
+    fn build_returns_result(&self) -> bool {
+        self.builder_attr.build_result
+            || self
+                .fields
+                .iter()
+                .any(|f| f.builder_attr.setter.validate.is_some() || f.builder_attr.group.is_some())
+    }
+
+    /// The generics of the struct that the error types of the validators refer to, which the build
+    /// error type is generic over - along with the ones their bounds refer to.
+    fn build_error_generics(&self) -> syn::Generics {
+        let mut used = GenericsCollector::default();
+        for field in &self.fields {
+            if let Some(validator) = &field.builder_attr.setter.validate {
+                used.visit_type(&validator.error_type);
+            }
+        }
+        let param_name = |param: &syn::GenericParam| match param {
+            syn::GenericParam::Type(ty) => ty.ident.to_string(),
+            syn::GenericParam::Const(cnst) => cnst.ident.to_string(),
+            syn::GenericParam::Lifetime(lifetime) => lifetime.lifetime.to_string(),
         };
+        let predicates = self
+            .generics
+            .where_clause
+            .as_ref()
+            .map_or_else(Vec::new, |where_clause| where_clause.predicates.iter().collect());
+        loop {
+            let count = used.names.len();
+            for param in &self.generics.params {
+                if used.names.contains(&param_name(param)) {
+                    used.visit_generic_param(param);
+                }
+            }
+            for predicate in &predicates {
+                let mut mentioned = GenericsCollector::default();
+                mentioned.visit_where_predicate(predicate);
+                if mentioned.names.iter().any(|name| used.names.contains(name)) {
+                    used.names.extend(mentioned.names);
+                }
+            }
+            if used.names.len() == count {
+                break;
+            }
+        }
 
-        builder_generics.push(syn::GenericArgument::Type(builder_generics_tuple.into()));
-        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let mut generics = self.generics.clone();
+        generics.params = generics
+            .params
+            .into_iter()
+            .filter(|param| used.names.contains(&param_name(param)))
+            .collect();
+        if let Some(where_clause) = &mut generics.where_clause {
+            where_clause.predicates = predicates
+                .into_iter()
+                .filter(|predicate| {
+                    let mut mentioned = GenericsCollector::default();
+                    mentioned.visit_where_predicate(predicate);
+                    mentioned.names.iter().any(|name| used.names.contains(name))
+                })
+                .cloned()
+                .collect();
+        }
+        generics
+    }
 
-        let early_build_error_type_name = syn::Ident::new(
-            &format!(
-                "{}_Error_Missing_required_field_{}",
-                builder_name,
-                strip_raw_ident_prefix(field_name.to_string())
-            ),
-            proc_macro2::Span::call_site(),
-        );
-        let early_build_error_message = format!("Missing required field {}", field_name);
+    /// The build error type, with its generics.
+    fn build_error_type(&self) -> TokenStream {
+        let error_type_name = self.build_error_type_name();
+        let generics = self.build_error_generics();
+        let (_, ty_generics, _) = generics.split_for_impl();
+        quote!(#error_type_name #ty_generics)
+    }
 
-        let build_method_name = self.build_method_name();
-        let build_method_visibility = self.build_method_visibility();
+    fn build_error_impl(&self) -> TokenStream {
+        let core = self.core_path();
+        let crate_path = self.crate_path();
+        let error_type_name = self.build_error_type_name();
+        let visibility = self.builder_type_visibility();
+        let generics = self.build_error_generics();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let validated_fields = self
+            .fields
+            .iter()
+            .filter_map(|field| Some((field, field.builder_attr.setter.validate.as_ref()?)))
+            .collect::<Vec<_>>();
+        let variants = validated_fields.iter().map(|(field, validator)| {
+            let name = &field.name;
+            let error_type = &validator.error_type;
+            let doc = format!("The validation of `{}` failed.", strip_raw_ident_prefix(name.to_string()));
+            quote! {
+                #[doc = #doc]
+                #name(#error_type),
+            }
+        });
+        let groups = self.field_groups().into_iter().map(|(group, _)| group).collect::<Vec<_>>();
+        let group_variants = groups.iter().map(|group| {
+            let doc = format!(
+                "More than one of the fields of the `{}` group was set - these are the first two.",
+                strip_raw_ident_prefix(group.to_string())
+            );
+            quote! {
+                #[doc = #doc]
+                #group(&'static str, &'static str),
+            }
+        });
+
+        // The errors of the validators only have to implement `Debug`, so that is what is shown.
+        let mut display_where_clause = generics.where_clause.clone().unwrap_or_else(|| syn::WhereClause {
+            where_token: Default::default(),
+            predicates: Default::default(),
+        });
+        let display_arms = validated_fields.iter().map(|(field, validator)| {
+            let name = &field.name;
+            let error_type = &validator.error_type;
+            display_where_clause
+                .predicates
+                .push(syn::parse_quote!(#error_type: #core::fmt::Debug));
+            let message = format!("validation of `{}` failed: {{:?}}", strip_raw_ident_prefix(name.to_string()));
+            quote!(Self::#name(ref error) => #core::write!(f, #message, error),)
+        });
+        let display_arms = display_arms.collect::<Vec<_>>();
+        let group_display_arms = groups.iter().map(|group| {
+            let message = format!(
+                "`{{}}` and `{{}}` can't both be set, since they are in the `{}` group",
+                strip_raw_ident_prefix(group.to_string())
+            );
+            quote!(Self::#group(first, second) => #core::write!(f, #message, first, second),)
+        });
+        let mut error_where_clause = display_where_clause.clone();
+        error_where_clause.predicates.push(syn::parse_quote!(Self: #core::fmt::Debug));
 
+        let doc = format!("Error returned when the build of a [`{}`] fails validation.", self.name);
         quote! {
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, non_snake_case)]
-            pub enum #early_build_error_type_name {}
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, missing_docs, clippy::panic)]
-            impl #impl_generics #builder_name < #( #builder_generics ),* > #where_clause {
-                #[deprecated(
-                    note = #early_build_error_message
-                )]
-                #build_method_visibility fn #build_method_name(self, _: #early_build_error_type_name) -> ! {
-                    panic!()
+            #[doc = #doc]
+            #[derive(Debug)]
+            #[allow(dead_code, non_camel_case_types)]
+            #visibility enum #error_type_name #generics #where_clause {
+                #( #variants )*
+                #( #group_variants )*
+            }
+
+            #[allow(dead_code, non_camel_case_types)]
+            impl #impl_generics #core::fmt::Display for #error_type_name #ty_generics #display_where_clause {
+                fn fmt(&self, f: &mut #core::fmt::Formatter<'_>) -> #core::fmt::Result {
+                    match *self {
+                        #( #display_arms )*
+                        #( #group_display_arms )*
+                    }
                 }
             }
+
+            #crate_path::__if_std! {
+                #[allow(dead_code, non_camel_case_types)]
+                impl #impl_generics #crate_path::StdError for #error_type_name #ty_generics #error_where_clause {}
+            }
         }
     }
 
-    fn build_method_name(&self) -> TokenStream {
-        self.builder_attr.build_method.common.get_name().unwrap_or(quote!(build))
+    /// An identifier for generated code that lives alongside the field names, changed as needed so
+    /// that it does not clash with any of them.
+    fn ident_not_clashing_with_fields(&self, base: &str) -> syn::Ident {
+        let mut name = base.to_owned();
+        while self.fields.iter().any(|f| f.name == name) {
+            name.push('_');
+        }
+        syn::Ident::new(&name, proc_macro2::Span::call_site())
     }
 
-    fn build_method_visibility(&self) -> TokenStream {
-        first_visibility(&[self.builder_attr.build_method.common.vis.as_ref(), Some(&public_visibility())])
+    /// Orders the fields so that the default of each field comes after the fields it refers to, and
+    /// otherwise keeps them in declaration order.
+    fn fields_in_default_order(&self) -> Result<Vec<&FieldInfo<'a>>, Error> {
+        let dependencies = self
+            .fields
+            .iter()
+            .map(|field| {
+                let mut idents = Vec::new();
+                if let Some(default) = &field.builder_attr.default {
+                    collect_variable_idents(default, &mut idents);
+                }
+                // A field referring to itself in its own default is referring to something else.
+                self.fields
+                    .iter()
+                    .filter(|f| f.ordinal != field.ordinal && idents.contains(&f.name))
+                    .map(|f| f.ordinal)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut ordered = Vec::with_capacity(self.fields.len());
+        let mut done = vec![false; self.fields.len()];
+        while ordered.len() < self.fields.len() {
+            let ready = (0..self.fields.len()).find(|&i| !done[i] && dependencies[i].iter().all(|&dependency| done[dependency]));
+            if let Some(i) = ready {
+                done[i] = true;
+                ordered.push(&self.fields[i]);
+                continue;
+            }
+
+            // Every field that is not done depends on another such field, so following these
+            // dependencies must eventually lead to a cycle.
+            let mut cycle = vec![done.iter().position(|&done| !done).unwrap()];
+            loop {
+                let last = *cycle.last().unwrap();
+                let next = dependencies[last]
+                    .iter()
+                    .copied()
+                    .find(|&dependency| !done[dependency])
+                    .unwrap();
+                if let Some(start) = cycle.iter().position(|&i| i == next) {
+                    cycle.drain(..start);
+                    break;
+                }
+                cycle.push(next);
+            }
+            let description = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|&i| format!("`{}`", self.fields[i].name))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            let first = &self.fields[cycle[0]];
+            return Err(Error::new_spanned(
+                first.builder_attr.default.as_ref().unwrap(),
+                format!("the defaults of fields {} depend on each other in a cycle", description),
+            ));
+        }
+        Ok(ordered)
     }
 
-    pub fn build_method_impl(&self) -> TokenStream {
+    pub fn build_method_impl(&self) -> Result<TokenStream, Error> {
+        let core = self.core_path();
+        let crate_path = self.crate_path();
         let StructInfo {
             ref name,
             ref builder_name,
@@ -428,23 +1843,14 @@ impl<'a> StructInfo<'a> {
             let mut generics = self.generics.clone();
             for field in self.included_fields() {
                 if field.builder_attr.default.is_some() {
-                    let trait_ref = syn::TraitBound {
-                        paren_token: None,
-                        lifetimes: None,
-                        modifier: syn::TraitBoundModifier::None,
-                        path: syn::PathSegment {
-                            ident: self.conversion_helper_trait_name.clone(),
-                            arguments: syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
-                                colon2_token: None,
-                                lt_token: Default::default(),
-                                args: make_punctuated_single(syn::GenericArgument::Type(field.ty.clone())),
-                                gt_token: Default::default(),
-                            }),
-                        }
-                        .into(),
-                    };
+                    let field_type = field.ty;
                     let mut generic_param: syn::TypeParam = field.generic_ident.clone().into();
-                    generic_param.bounds.push(trait_ref.into());
+                    generic_param
+                        .bounds
+                        .push(syn::parse_quote!(#crate_path::Optional<#field_type>));
+                    if field.builder_attr.group.is_some() {
+                        generic_param.bounds.push(syn::parse_quote!(#crate_path::IsSet));
+                    }
                     generics.params.push(generic_param.into());
                 }
             }
@@ -453,6 +1859,16 @@ impl<'a> StructInfo<'a> {
         let (impl_generics, _, _) = generics.split_for_impl();
 
         let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+        // The bounds of `build_method(bounds(...))` only apply to building, not to the setters.
+        let where_clause = if self.builder_attr.build_method.bounds.is_empty() {
+            where_clause.cloned()
+        } else {
+            let mut where_clause = where_clause.cloned().unwrap_or_else(|| syn::parse_quote!(where));
+            where_clause
+                .predicates
+                .extend(self.builder_attr.build_method.bounds.iter().cloned());
+            Some(where_clause)
+        };
 
         let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
             args.push(syn::GenericArgument::Type(
@@ -467,31 +1883,72 @@ impl<'a> StructInfo<'a> {
             ));
         });
 
-        let descructuring = self.included_fields().map(|f| f.name);
-
-        let helper_trait_name = &self.conversion_helper_trait_name;
+        let descructuring = self.included_fields().map(|f| &f.name).collect::<Vec<_>>();
 
-        // The default of a field can refer to earlier-defined fields, which we handle by
-        // writing out a bunch of `let` statements first, which can each refer to earlier ones.
-        // This means that field ordering may actually be significant, which isn't ideal. We could
-        // relax that restriction by calculating a DAG of field default dependencies and
-        // reordering based on that, but for now this much simpler thing is a reasonable approach.
-        let assignments = self.fields.iter().map(|field| {
+        // The default of a field can refer to other fields, which we handle by writing out a bunch
+        // of `let` statements first, ordered so that each one comes after the fields its default
+        // refers to.
+        let error_type_name = self.build_error_type_name();
+        let error_type = self.build_error_type();
+        let struct_type = quote!(<#name #ty_generics>);
+        let assignments = self.fields_in_default_order()?.into_iter().map(|field| {
             let name = &field.name;
-            if let Some(ref default) = field.builder_attr.default {
+            let ty = field.ty;
+            // The explicit type annotations help inference of default expressions in generic structs.
+            let assignment = if let Some(ref default) = field.builder_attr.default {
+                let default = match self.exposed_default_name(field) {
+                    Some(const_name) => quote_spanned!(default.span()=> #struct_type::#const_name),
+                    None => default.to_token_stream(),
+                };
                 if field.builder_attr.setter.skip.is_some() {
-                    quote!(let #name = #default;)
+                    quote!(let #name: #ty = #default;)
                 } else {
-                    quote!(let #name = #helper_trait_name::into_value(#name, || #default);)
+                    // The default is evaluated in the build method itself rather than in a closure,
+                    // so that a `#[track_caller]` on the build method applies to panics in it.
+                    quote! {
+                        let #name: #ty = match #crate_path::Optional::<#ty>::into_option(#name) {
+                            #core::option::Option::Some(#name) => #name,
+                            #core::option::Option::None => #default,
+                        };
+                    }
                 }
             } else {
                 quote!(let #name = #name.0;)
+            };
+            if let Some(validator) = &field.builder_attr.setter.validate {
+                let closure = &validator.closure;
+                quote! {
+                    #assignment
+                    if let #core::result::Result::Err(error) = __typed_builder_validate(&#name, #closure) {
+                        return #core::result::Result::Err(#error_type_name::#name(error));
+                    }
+                }
+            } else {
+                assignment
             }
         });
-        let field_names = self.fields.iter().map(|field| field.name);
+        let assignments = assignments.collect::<Vec<_>>();
+        // The struct literal lists the fields in declaration order, regardless of the order the
+        // defaults were evaluated in.
+        let field_inits = self.fields.iter().map(|field| {
+            let FieldInfo { name, member, .. } = field;
+            match member {
+                syn::Member::Named(_) => quote!(#name),
+                syn::Member::Unnamed(_) => quote!(#member: #name),
+            }
+        });
+        let constructed_path = self.constructed_path();
+        let constructed = quote! {
+            #[allow(deprecated)]
+            #constructed_path {
+                #( #field_inits ),*
+            }
+        };
 
         let build_method_name = self.build_method_name();
         let build_method_visibility = self.build_method_visibility();
+        let build_method_attrs = self.builder_attr.build_method.common.get_attrs();
+        let inline = self.inline_attr();
         let build_method_doc = if self.builder_attr.doc {
             self.builder_attr
                 .build_method
@@ -509,31 +1966,191 @@ impl<'a> StructInfo<'a> {
             ),
             IntoSetting::TypeConversionToSpecificType(into) => (None, into.to_token_stream(), None),
         };
+        let (output_type, built) = if self.builder_attr.build_as_tuple.is_some() {
+            let field_types = self.fields.iter().map(|f| f.ty);
+            let field_names = self.fields.iter().map(|f| &f.name);
+            (quote!(( #( #field_types, )* )), quote!(( #( #field_names, )* )))
+        } else {
+            (output_type, quote!(#constructed.into()))
+        };
+        let (output_type, built, build_error) = if self.build_returns_result() {
+            (
+                quote!(#core::result::Result<#output_type, #error_type>),
+                quote!(#core::result::Result::Ok(#built)),
+                self.build_error_impl(),
+            )
+        } else {
+            (output_type, built, quote!())
+        };
+        let zero_cost_check = if self.builder_attr.assert_zero_cost {
+            quote!(let () = Self::__TYPED_BUILDER_ZERO_COST;)
+        } else {
+            quote!()
+        };
+        // Whether fields were set is checked before the defaults are evaluated, while the field
+        // bindings still hold the builder's type-state.
+        let group_checks = self.field_groups().into_iter().map(|(group, fields)| {
+            let names = fields.iter().map(|f| &f.name);
+            let name_strs = fields.iter().map(|f| strip_raw_ident_prefix(f.name.to_string()));
+            quote! {
+                {
+                    let set_fields = [#( (#name_strs, #crate_path::IsSet::is_set(&#names)) ),*];
+                    let mut first_set = #core::option::Option::None;
+                    for &(name, is_set) in &set_fields {
+                        if is_set {
+                            if let #core::option::Option::Some(first_set) = first_set {
+                                return #core::result::Result::Err(#error_type_name::#group(first_set, name));
+                            }
+                            first_set = #core::option::Option::Some(name);
+                        }
+                    }
+                }
+            }
+        });
+        let group_checks = quote!(#( #group_checks )*);
+        // Passing the validator closures through a function lets rustc infer their argument type.
+        let has_validators = self.fields.iter().any(|f| f.builder_attr.setter.validate.is_some());
+        let validate_helper = if has_validators {
+            quote! {
+                fn __typed_builder_validate<T: ?Sized, E>(
+                    value: &T,
+                    validator: impl #core::ops::FnOnce(&T) -> #core::result::Result<(), E>,
+                ) -> #core::result::Result<(), E> {
+                    validator(value)
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        // The builder can also be converted with the standard conversion traits, which call the build
+        // method - so they are `TryFrom` when it can fail. They are only implemented when the build
+        // method can produce the struct itself.
+        let build_struct = match &self.builder_attr.build_method.into {
+            IntoSetting::NoConversion => Some(quote!(builder.#build_method_name())),
+            IntoSetting::GenericConversion => Some(quote!(builder.#build_method_name::<Self>())),
+            IntoSetting::TypeConversionToSpecificType(_) => None,
+        };
+        let build_struct = build_struct.filter(|_| self.builder_attr.build_as_tuple.is_none());
+        let can_fail = has_validators || self.fields.iter().any(|f| f.builder_attr.group.is_some());
+        let conversion_impl = if self.builder_attr.build_method.no_from || build_struct.is_none() {
+            quote!()
+        } else if can_fail {
+            quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
+                impl #impl_generics #core::convert::TryFrom<#builder_name #modified_ty_generics> for #name #ty_generics #where_clause {
+                    type Error = #error_type;
+
+                    #inline
+                    fn try_from(builder: #builder_name #modified_ty_generics) -> #core::result::Result<Self, #error_type> {
+                        #build_struct
+                    }
+                }
+            }
+        } else {
+            // Only validators and groups can fail, so the error of `build_result` alone has no values.
+            let build_struct = if self.build_returns_result() {
+                quote!(match #build_struct {
+                    #core::result::Result::Ok(built) => built,
+                    #core::result::Result::Err(error) => match error {},
+                })
+            } else {
+                build_struct.to_token_stream()
+            };
+            quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
+                impl #impl_generics #core::convert::From<#builder_name #modified_ty_generics> for #name #ty_generics #where_clause {
+                    #inline
+                    fn from(builder: #builder_name #modified_ty_generics) -> Self {
+                        #build_struct
+                    }
+                }
+            }
+        };
+
+        // `build_into` runs the same `let` statements as the build method, and then writes each field
+        // into the destination instead of assembling the struct.
+        let build_into_method = if self.builder_attr.build_into {
+            let dest = self.ident_not_clashing_with_fields("dest");
+            let ptr = self.ident_not_clashing_with_fields("__ptr");
+            let writes = self.fields.iter().map(|field| {
+                let FieldInfo { name, member, .. } = field;
+                if self.is_packed {
+                    quote!(#core::ptr::write_unaligned(#core::ptr::addr_of_mut!((*#ptr).#member), #name);)
+                } else {
+                    quote!(#core::ptr::write(#core::ptr::addr_of_mut!((*#ptr).#member), #name);)
+                }
+            });
+            let (output_type, built) = if self.build_returns_result() {
+                (
+                    quote!(#core::result::Result<&mut #name #ty_generics, #error_type>),
+                    quote!(#core::result::Result::Ok(#core::mem::MaybeUninit::assume_init_mut(#dest))),
+                )
+            } else {
+                (
+                    quote!(&mut #name #ty_generics),
+                    quote!(#core::mem::MaybeUninit::assume_init_mut(#dest)),
+                )
+            };
+            quote! {
+                #[allow(deprecated, clippy::default_trait_access, clippy::redundant_closure)]
+                #inline
+                #build_method_visibility fn build_into(self, #dest: &mut #core::mem::MaybeUninit<#name #ty_generics>) -> #output_type {
+                    #validate_helper
+                    let ( #(#descructuring,)* ) = self.fields;
+                    #group_checks
+                    #( #assignments )*
+
+                    let #ptr = #core::mem::MaybeUninit::as_mut_ptr(#dest);
+                    // SAFETY: `ptr` points to the destination, and all the fields are written before
+                    // it is assumed to be initialized.
+                    unsafe {
+                        #( #writes )*
+                        #built
+                    }
+                }
+            }
+        } else {
+            quote!()
+        };
+
+        Ok(quote!(
+            #build_error
 
-        quote!(
-            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[allow(dead_code, non_camel_case_types, missing_docs, clippy::type_complexity)]
             impl #impl_generics #builder_name #modified_ty_generics #where_clause {
                 #build_method_doc
-                #[allow(clippy::default_trait_access)]
+                #build_method_attrs
+                #[allow(clippy::default_trait_access, clippy::redundant_closure)]
+                #inline
                 #build_method_visibility fn #build_method_name #build_method_generic (self) -> #output_type #build_method_where_clause {
+                    #zero_cost_check
+                    #validate_helper
                     let ( #(#descructuring,)* ) = self.fields;
+                    #group_checks
                     #( #assignments )*
 
-                    #[allow(deprecated)]
-                    #name {
-                        #( #field_names ),*
-                    }.into()
+                    #built
                 }
+
+                #build_into_method
             }
-        )
+
+            #conversion_impl
+        ))
     }
 }
 
+/// Keys accepted inside `builder_method(...)`, `builder_type(...)` and `build_method(...)`.
+const DECLARATION_KEYS: &[&str] = &["vis = \"...\"", "name = ...", "doc = \"...\"", "attr(...)"];
+
 #[derive(Debug, Default, Clone)]
 pub struct CommonDeclarationSettings {
     pub vis: Option<syn::Visibility>,
     pub name: Option<syn::Expr>,
     pub doc: Option<syn::Expr>,
+    /// Attributes to put on the declaration as-is, from `attr(...)`.
+    pub attrs: Vec<syn::Expr>,
 }
 impl CommonDeclarationSettings {
     fn apply_meta(&mut self, expr: syn::Expr) -> Result<(), Error> {
@@ -543,6 +2160,7 @@ impl CommonDeclarationSettings {
                     expr_to_single_string(&assign.left).ok_or_else(|| Error::new_spanned(&assign.left, "Expected identifier"))?;
                 match name.as_str() {
                     "vis" => {
+                        ensure_not_set(&self.vis, &assign.left)?;
                         if let syn::Expr::Lit(expr_lit) = &*assign.right {
                             if let syn::Lit::Str(ref s) = expr_lit.lit {
                                 self.vis = Some(syn::parse_str(&s.value()).expect("invalid visibility found"));
@@ -554,20 +2172,52 @@ impl CommonDeclarationSettings {
                         Ok(())
                     }
                     "name" => {
-                        self.name = Some(*assign.right);
+                        ensure_not_set(&self.name, &assign.left)?;
+                        let name = if let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(name),
+                            ..
+                        }) = &*assign.right
+                        {
+                            let ident: syn::Ident = name
+                                .parse()
+                                .map_err(|_| Error::new_spanned(name, format!("{:?} is not a valid identifier", name.value())))?;
+                            syn::Expr::Path(syn::ExprPath {
+                                attrs: Vec::new(),
+                                qself: None,
+                                path: ident.into(),
+                            })
+                        } else {
+                            *assign.right
+                        };
+                        self.name = Some(name);
                         Ok(())
                     }
                     "doc" => {
+                        ensure_not_set(&self.doc, &assign.left)?;
                         self.doc = Some(*assign.right);
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(&assign, format!("Unknown parameter {:?}", name))),
+                    _ => Err(unknown_key_error(&assign.left, DECLARATION_KEYS)),
+                }
+            }
+            syn::Expr::Call(call) if expr_to_single_string(&call.func).as_deref() == Some("attr") => {
+                if call.args.is_empty() {
+                    return Err(Error::new_spanned(call, "Expected attr(…)"));
                 }
+                self.attrs.extend(call.args);
+                Ok(())
             }
+            syn::Expr::Path(path) => Err(unknown_key_error(&path, DECLARATION_KEYS)),
+            syn::Expr::Call(call) => Err(unknown_key_error(&call.func, DECLARATION_KEYS)),
             _ => Err(Error::new_spanned(expr, "Expected (<...>=<...>)")),
         }
     }
 
+    fn get_attrs(&self) -> TokenStream {
+        let attrs = &self.attrs;
+        quote!(#( #[#attrs] )*)
+    }
+
     fn get_name(&self) -> Option<TokenStream> {
         self.name.as_ref().map(|name| name.to_token_stream())
     }
@@ -583,9 +2233,10 @@ impl CommonDeclarationSettings {
 }
 
 /// Setting of the `into` argument.
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone)]
 pub enum IntoSetting {
     /// Do not run any conversion on the built value.
+    #[default]
     NoConversion,
     /// Convert the build value into the generic parameter passed to the `build` method.
     GenericConversion,
@@ -593,18 +2244,23 @@ pub enum IntoSetting {
     TypeConversionToSpecificType(syn::ExprPath),
 }
 
-impl Default for IntoSetting {
-    fn default() -> Self {
-        Self::NoConversion
-    }
-}
-
 #[derive(Debug, Default, Clone)]
 pub struct BuildMethodSettings {
     pub common: CommonDeclarationSettings,
 
+    /// Extra where predicates for the build method, from `bounds(...)`.
+    pub bounds: Vec<syn::WherePredicate>,
+
     /// Whether to convert the built type into another while finishing the build.
     pub into: IntoSetting,
+
+    /// Whether `no_from` was given, which leaves out the `From` (or `TryFrom`) implementation for
+    /// converting the builder into the struct.
+    pub no_from: bool,
+
+    /// The name of the error type the build method returns when it returns a `Result`, instead of
+    /// `{Builder}Error`.
+    pub error: Option<syn::Ident>,
 }
 
 impl BuildMethodSettings {
@@ -614,12 +2270,25 @@ impl BuildMethodSettings {
                 let name =
                     expr_to_single_string(&assign.left).ok_or_else(|| Error::new_spanned(&assign.left, "Expected identifier"))?;
                 if name.as_str() == "into" {
+                    self.ensure_into_not_set(&assign.left)?;
                     let expr_path = match assign.right.as_ref() {
                         syn::Expr::Path(expr_path) => expr_path,
                         _ => return Err(Error::new_spanned(&assign.right, "Expected path expression type")),
                     };
                     self.into = IntoSetting::TypeConversionToSpecificType(expr_path.clone());
                     Ok(())
+                } else if name.as_str() == "error" {
+                    ensure_not_set(&self.error, &assign.left)?;
+                    let error = match assign.right.as_ref() {
+                        syn::Expr::Path(path) => path.path.get_ident().cloned(),
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(name),
+                            ..
+                        }) => name.parse().ok(),
+                        _ => None,
+                    };
+                    self.error = Some(error.ok_or_else(|| Error::new_spanned(&assign.right, "Expected identifier"))?);
+                    Ok(())
                 } else {
                     self.common.apply_meta(expr)
                 }
@@ -627,22 +2296,98 @@ impl BuildMethodSettings {
             syn::Expr::Path(path) => {
                 let name = path_to_single_string(&path.path).ok_or_else(|| Error::new_spanned(path, "Expected identifier"))?;
                 if name.as_str() == "into" {
+                    self.ensure_into_not_set(&expr)?;
                     self.into = IntoSetting::GenericConversion;
                     Ok(())
+                } else if name.as_str() == "no_from" {
+                    self.no_from = true;
+                    Ok(())
                 } else {
                     self.common.apply_meta(expr)
                 }
             }
+            syn::Expr::Verbatim(tokens) => {
+                self.bounds.extend(parse_bounds.parse2(tokens.clone())?);
+                Ok(())
+            }
             _ => self.common.apply_meta(expr),
         }
     }
+
+    fn ensure_into_not_set(&self, key: &syn::Expr) -> Result<(), Error> {
+        match self.into {
+            IntoSetting::NoConversion => Ok(()),
+            _ => Err(Error::new_spanned(key, "Illegal setting - `into` is already set")),
+        }
+    }
+}
+
+/// Collects the names of the generic parameters (and anything else named by a single identifier)
+/// that a type, bound or where predicate refers to.
+#[derive(Default)]
+struct GenericsCollector {
+    names: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for GenericsCollector {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if let Some(first) = path.segments.first() {
+            if path.leading_colon.is_none() && !self.names.contains(&first.ident.to_string()) {
+                self.names.push(first.ident.to_string());
+            }
+        }
+        visit::visit_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        if !self.names.contains(&lifetime.to_string()) {
+            self.names.push(lifetime.to_string());
+        }
+    }
 }
 
+/// Keys accepted in a type's `#[builder(...)]`, listed when an unknown one is given.
+const TYPE_KEYS: &[&str] = &[
+    "doc",
+    "doc_examples",
+    "build_result",
+    "builder_impl_default",
+    "inline",
+    "into_builder",
+    "introspect",
+    "field_metadata",
+    "phased",
+    "mergeable",
+    "expose_defaults",
+    "getters",
+    "build_into",
+    "assert_zero_cost",
+    "build_as_tuple",
+    "test_builder",
+    "no_must_use",
+    "crate = ...",
+    "crate_module = ...",
+    "module = \"...\"",
+    "rename_all = \"...\"",
+    "field_defaults(...)",
+    "setter(...)",
+    "derive(...)",
+    "cfg(...)",
+    "builder_method(...)",
+    "builder_type(...)",
+    "build_method(...)",
+    "mutators(...)",
+    "extra_impl { ... }",
+];
+
 #[derive(Debug, Default)]
 pub struct TypeBuilderAttr<'a> {
     /// Whether to show docs for the `TypeBuilder` type (rather than hiding them).
     pub doc: bool,
 
+    /// Whether to add a usage example to the docs of the builder type (which also shows them).
+    pub doc_examples: bool,
+
     /// Customize builder method, ex. visibility, name
     pub builder_method: CommonDeclarationSettings,
 
@@ -653,6 +2398,86 @@ pub struct TypeBuilderAttr<'a> {
     pub build_method: BuildMethodSettings,
 
     pub field_defaults: FieldBuilderAttr<'a>,
+
+    /// Whether the build method returns a `Result`, even if no field has a validator.
+    pub build_result: bool,
+
+    /// Whether to implement `Default` for the empty builder.
+    pub builder_impl_default: bool,
+
+    /// Whether to mark the setters and the build method `#[inline]`.
+    pub inline: bool,
+
+    /// Whether to generate an `into_builder` method that turns the struct back into a builder.
+    pub into_builder: bool,
+
+    /// Whether to implement `Debug` for the builder type. (`Clone` is always implemented.)
+    pub derive_debug: bool,
+
+    /// Whether to implement `PartialEq` for the builder type.
+    pub derive_partial_eq: bool,
+
+    /// Whether to implement `Eq` for the builder type.
+    pub derive_eq: bool,
+
+    /// Whether to implement `Hash` for the builder type.
+    pub derive_hash: bool,
+
+    /// Whether to generate a `field_states` method on the builder that reports which fields are set.
+    pub introspect: bool,
+
+    /// Whether to generate a `REQUIRED_BUILDER_FIELDS` const on the struct.
+    pub field_metadata: bool,
+
+    /// Whether to expose the literal defaults of fields as associated consts on the struct.
+    pub expose_defaults: bool,
+
+    /// Whether the setters of the optional fields can only be called after all the required fields
+    /// were set.
+    pub phased: bool,
+
+    /// Whether all the fields are stored as `Option`s, and the builder gets a `merge` method.
+    pub mergeable: bool,
+
+    /// Where `build_as_tuple` was given, which makes the build method return the values of the
+    /// fields as a tuple instead of the struct.
+    pub build_as_tuple: Option<proc_macro2::Span>,
+
+    /// Whether to generate a `get_x` method on the builder for each field, available once it is set.
+    pub getters: bool,
+
+    /// Whether to generate a `build_into` method that writes the fields in place into a
+    /// `MaybeUninit`.
+    pub build_into: bool,
+
+    /// Whether to check at compile time that the builder is no larger than the values of its fields.
+    pub assert_zero_cost: bool,
+
+    /// Where `test_builder` was given, which generates a `test_builder` method on the struct that
+    /// requires all the fields to have defaults.
+    pub test_builder: Option<proc_macro2::Span>,
+
+    /// Whether to leave out the `#[must_use]` attribute of the builder type.
+    pub no_must_use: bool,
+
+    /// Custom methods on the builder type that can mutate the fields that were already set.
+    pub mutators: Vec<Mutator>,
+
+    /// Items put as-is in an `impl` block of the builder type that is generic over the state of all
+    /// the fields.
+    pub extra_impl: Vec<syn::ImplItem>,
+
+    /// Path to the `core` crate, for when `::core` can't be used.
+    pub crate_module: Option<syn::Path>,
+
+    /// Path to this crate, for when `::typed_builder` can't be used.
+    pub crate_path: Option<syn::Path>,
+
+    /// A `cfg` predicate that all the generated items are gated behind.
+    pub cfg: Option<syn::Expr>,
+
+    /// A module that the generated items are put in, instead of the struct's module.
+    pub module: Option<syn::Ident>,
 }
 
 impl<'a> TypeBuilderAttr<'a> {
@@ -678,6 +2503,12 @@ impl<'a> TypeBuilderAttr<'a> {
             result.doc = true;
         }
 
+        if let Some(span) = result.build_as_tuple {
+            if !matches!(result.build_method.into, IntoSetting::NoConversion) {
+                return Err(Error::new(span, "`build_as_tuple` can't be used with `build_method(into)`"));
+            }
+        }
+
         Ok(result)
     }
 
@@ -700,7 +2531,45 @@ impl<'a> TypeBuilderAttr<'a> {
                     "builder_method_doc" => Err(gen_structure_depracation_error("builder_method", "doc")),
                     "builder_type_doc" => Err(gen_structure_depracation_error("builder_type", "doc")),
                     "build_method_doc" => Err(gen_structure_depracation_error("build_method", "doc")),
-                    _ => Err(Error::new_spanned(&assign, format!("Unknown parameter {:?}", name))),
+                    "rename_all" => {
+                        ensure_not_set(&self.field_defaults.setter.rename_all, &assign.left)?;
+                        self.field_defaults.setter.rename_all = Some(RenameRule::parse(&assign.right)?);
+                        Ok(())
+                    }
+                    "module" => {
+                        ensure_not_set(&self.module, &assign.left)?;
+                        let module = expr_to_lit_string(&assign.right)?;
+                        self.module = Some(
+                            syn::parse_str::<syn::Ident>(&module)
+                                .map(|mut ident| {
+                                    ident.set_span(assign.right.span());
+                                    ident
+                                })
+                                .map_err(|_| {
+                                    Error::new_spanned(&assign.right, format!("{:?} is not a valid identifier", module))
+                                })?,
+                        );
+                        Ok(())
+                    }
+                    "crate" | "crate_module" => {
+                        let target = if name == "crate" {
+                            &mut self.crate_path
+                        } else {
+                            &mut self.crate_module
+                        };
+                        ensure_not_set(target, &assign.left)?;
+                        let path = match *assign.right {
+                            syn::Expr::Path(path) => path.path,
+                            syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(path),
+                                ..
+                            }) => path.parse()?,
+                            right => return Err(Error::new_spanned(right, "Expected a path")),
+                        };
+                        *target = Some(path);
+                        Ok(())
+                    }
+                    _ => Err(unknown_key_error(&assign.left, TYPE_KEYS)),
                 }
             }
             syn::Expr::Path(path) => {
@@ -710,7 +2579,71 @@ impl<'a> TypeBuilderAttr<'a> {
                         self.doc = true;
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(&path, format!("Unknown parameter {:?}", name))),
+                    "doc_examples" => {
+                        self.doc_examples = true;
+                        Ok(())
+                    }
+                    "build_result" => {
+                        self.build_result = true;
+                        Ok(())
+                    }
+                    "builder_impl_default" => {
+                        self.builder_impl_default = true;
+                        Ok(())
+                    }
+                    "inline" => {
+                        self.inline = true;
+                        Ok(())
+                    }
+                    "into_builder" => {
+                        self.into_builder = true;
+                        Ok(())
+                    }
+                    "introspect" => {
+                        self.introspect = true;
+                        Ok(())
+                    }
+                    "field_metadata" => {
+                        self.field_metadata = true;
+                        Ok(())
+                    }
+                    "phased" => {
+                        self.phased = true;
+                        Ok(())
+                    }
+                    "mergeable" => {
+                        self.mergeable = true;
+                        Ok(())
+                    }
+                    "expose_defaults" => {
+                        self.expose_defaults = true;
+                        Ok(())
+                    }
+                    "build_into" => {
+                        self.build_into = true;
+                        Ok(())
+                    }
+                    "getters" => {
+                        self.getters = true;
+                        Ok(())
+                    }
+                    "assert_zero_cost" => {
+                        self.assert_zero_cost = true;
+                        Ok(())
+                    }
+                    "build_as_tuple" => {
+                        self.build_as_tuple = Some(path.span());
+                        Ok(())
+                    }
+                    "test_builder" => {
+                        self.test_builder = Some(path.span());
+                        Ok(())
+                    }
+                    "no_must_use" => {
+                        self.no_must_use = true;
+                        Ok(())
+                    }
+                    _ => Err(unknown_key_error(&path, TYPE_KEYS)),
                 }
             }
             syn::Expr::Call(call) => {
@@ -731,6 +2664,40 @@ impl<'a> TypeBuilderAttr<'a> {
                         }
                         Ok(())
                     }
+                    "setter" => self.field_defaults.apply_meta(syn::Expr::Call(call)),
+                    "derive" => {
+                        for arg in call.args {
+                            match expr_to_single_string(&arg).as_deref() {
+                                // The builder is always `Clone`.
+                                Some("Clone") => {}
+                                Some("Debug") => self.derive_debug = true,
+                                Some("PartialEq") => self.derive_partial_eq = true,
+                                Some("Eq") => self.derive_eq = true,
+                                Some("Hash") => self.derive_hash = true,
+                                _ => {
+                                    return Err(Error::new_spanned(
+                                        arg,
+                                        "Only Clone, Debug, PartialEq, Eq and Hash can be derived for the builder",
+                                    ))
+                                }
+                            }
+                        }
+                        if self.derive_eq && !self.derive_partial_eq {
+                            return Err(Error::new_spanned(
+                                &call.func,
+                                "deriving Eq for the builder requires deriving PartialEq",
+                            ));
+                        }
+                        Ok(())
+                    }
+                    "cfg" => {
+                        ensure_not_set(&self.cfg, &call.func)?;
+                        if call.args.len() != 1 {
+                            return Err(Error::new_spanned(&call, "Expected a single cfg predicate - cfg(...)"));
+                        }
+                        self.cfg = call.args.into_iter().next();
+                        Ok(())
+                    }
                     "builder_method" => {
                         for arg in call.args {
                             self.builder_method.apply_meta(arg)?;
@@ -749,11 +2716,17 @@ impl<'a> TypeBuilderAttr<'a> {
                         }
                         Ok(())
                     }
-                    _ => Err(Error::new_spanned(
-                        &call.func,
-                        format!("Illegal builder setting group name {}", subsetting_name),
-                    )),
+                    _ => Err(unknown_key_error(&call.func, TYPE_KEYS)),
+                }
+            }
+            syn::Expr::Verbatim(tokens) => {
+                let is_extra_impl = matches!(tokens.clone().into_iter().next(), Some(proc_macro2::TokenTree::Ident(name)) if name == "extra_impl");
+                if is_extra_impl {
+                    self.extra_impl.extend(parse_extra_impl.parse2(tokens)?);
+                } else {
+                    self.mutators.extend(parse_mutators.parse2(tokens)?);
                 }
+                Ok(())
             }
             _ => Err(Error::new_spanned(expr, "Expected (<...>=<...>)")),
         }
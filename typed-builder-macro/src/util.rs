@@ -1,5 +1,10 @@
+use proc_macro2::{Spacing, TokenStream, TokenTree};
 use quote::ToTokens;
-use syn::{parse::Parser, Error};
+use syn::{
+    parse::{ParseStream, Parser},
+    visit::{self, Visit},
+    Error,
+};
 
 pub fn path_to_single_string(path: &syn::Path) -> Option<String> {
     if path.leading_colon.is_some() {
@@ -25,6 +30,93 @@ pub fn expr_to_single_string(expr: &syn::Expr) -> Option<String> {
     }
 }
 
+/// Returns `T` if the type is `Wrapper<T>`. The wrapper is matched by the last segment of its path,
+/// so it can be written with any path - e.g. `Option<T>`, `core::option::Option<T>` or
+/// `::std::option::Option<T>`. Type aliases can't be seen through.
+pub fn extract_wrapped_type<'a>(ty: &'a syn::Type, wrapper: &str) -> Option<&'a syn::Type> {
+    let (segment, generic_params) = last_segment_with_generics(ty)?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    if let syn::GenericArgument::Type(ty) = generic_params.args.first()? {
+        Some(ty)
+    } else {
+        None
+    }
+}
+
+/// Returns `T` if the type is a generic type with `T` as its only generic argument (e.g. `Arc<T>`
+/// or `Cell<T>`), whatever the type is.
+pub fn extract_single_type_argument(ty: &syn::Type) -> Option<&syn::Type> {
+    let (_, generic_params) = last_segment_with_generics(ty)?;
+    match generic_params.args.iter().collect::<Vec<_>>()[..] {
+        [syn::GenericArgument::Type(ty)] => Some(ty),
+        _ => None,
+    }
+}
+
+fn last_segment_with_generics(ty: &syn::Type) -> Option<(&syn::PathSegment, &syn::AngleBracketedGenericArguments)> {
+    let path = match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        syn::Type::Group(group) => return last_segment_with_generics(&group.elem),
+        syn::Type::Paren(paren) => return last_segment_with_generics(&paren.elem),
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(generic_params) = &segment.arguments else {
+        return None;
+    };
+    Some((segment, generic_params))
+}
+
+/// The builder type that `TypedBuilder` generates for a type by default - e.g. `FooBuilder<T>` for
+/// `Foo<T>`. The builder's type-state generic is left to its default, which is the unset state.
+pub fn default_builder_type(ty: &syn::Type) -> Option<syn::Type> {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let mut type_path = type_path.clone();
+            let segment = type_path.path.segments.last_mut()?;
+            segment.ident = syn::Ident::new(&format!("{}Builder", segment.ident), segment.ident.span());
+            Some(syn::Type::Path(type_path))
+        }
+        syn::Type::Group(group) => default_builder_type(&group.elem),
+        syn::Type::Paren(paren) => default_builder_type(&paren.elem),
+        _ => None,
+    }
+}
+
+/// The path of a generic type without its generic arguments - e.g. `std::boxed::Box` for
+/// `std::boxed::Box<T>` - for calling its constructor with the arguments left to inference.
+pub fn path_without_generic_arguments(ty: &syn::Type) -> Option<syn::Path> {
+    match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let mut path = type_path.path.clone();
+            path.segments.last_mut()?.arguments = syn::PathArguments::None;
+            Some(path)
+        }
+        syn::Type::Group(group) => path_without_generic_arguments(&group.elem),
+        syn::Type::Paren(paren) => path_without_generic_arguments(&paren.elem),
+        _ => None,
+    }
+}
+
+/// Whether the attributes include `#[repr(packed)]` (or `#[repr(packed(N))]`), which means the
+/// fields may not be aligned.
+pub fn is_repr_packed(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident("repr")).any(|attr| {
+        let mut packed = false;
+        // Unknown `repr` arguments are left for rustc to report.
+        let _ = attr.parse_nested_meta(|meta| {
+            packed |= meta.path.is_ident("packed");
+            if meta.input.peek(syn::token::Paren) {
+                let _: proc_macro2::Group = meta.input.parse()?;
+            }
+            Ok(())
+        });
+        packed
+    })
+}
+
 pub fn ident_to_type(ident: syn::Ident) -> syn::Type {
     let mut path = syn::Path {
         leading_colon: None,
@@ -63,12 +155,6 @@ pub fn empty_type_tuple() -> syn::TypeTuple {
     }
 }
 
-pub fn make_punctuated_single<T, P: Default>(value: T) -> syn::punctuated::Punctuated<T, P> {
-    let mut punctuated = syn::punctuated::Punctuated::new();
-    punctuated.push(value);
-    punctuated
-}
-
 pub fn modify_types_generics_hack<F>(ty_generics: &syn::TypeGenerics, mut mutator: F) -> syn::AngleBracketedGenericArguments
 where
     F: FnMut(&mut syn::punctuated::Punctuated<syn::GenericArgument, syn::token::Comma>),
@@ -91,6 +177,84 @@ pub fn strip_raw_ident_prefix(mut name: String) -> String {
     name
 }
 
+/// Converts a `CamelCase` name (e.g. of an enum variant) to `snake_case`.
+pub fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && 0 < i {
+            let prev = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev != '_' && (prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lowercase)) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// A case convention for the setters of all the fields, from `#[builder(rename_all = "...")]`.
+#[derive(Debug, Clone, Copy)]
+pub enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    pub fn parse(expr: &syn::Expr) -> Result<Self, Error> {
+        const RULES: [(&str, RenameRule); 6] = [
+            ("lowercase", RenameRule::Lower),
+            ("UPPERCASE", RenameRule::Upper),
+            ("PascalCase", RenameRule::Pascal),
+            ("camelCase", RenameRule::Camel),
+            ("snake_case", RenameRule::Snake),
+            ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+        ];
+        let name = expr_to_lit_string(expr)?;
+        if let Some((_, rule)) = RULES.iter().find(|(rule_name, _)| *rule_name == name) {
+            return Ok(*rule);
+        }
+        let message = if name.contains('-') {
+            format!("`{}` can't be used for method names", name)
+        } else {
+            let rule_names = RULES.iter().map(|(rule_name, _)| *rule_name).collect::<Vec<_>>();
+            format!("Unknown case `{}` - expected one of: {}", name, rule_names.join(", "))
+        };
+        Err(Error::new_spanned(expr, message))
+    }
+
+    /// Renames a `snake_case` field name. Leading underscores are kept as they are.
+    pub fn apply_to_field(self, name: &str) -> String {
+        let trimmed = name.trim_start_matches('_');
+        let leading_underscores = &name[..name.len() - trimmed.len()];
+        let renamed = match self {
+            RenameRule::Lower | RenameRule::Snake => trimmed.to_owned(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => trimmed.to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let mut result = String::new();
+                for (i, word) in trimmed.split('_').enumerate() {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        if i == 0 && matches!(self, RenameRule::Camel) {
+                            result.push(first);
+                        } else {
+                            result.extend(first.to_uppercase());
+                        }
+                        result.push_str(chars.as_str());
+                    }
+                }
+                result
+            }
+        };
+        format!("{}{}", leading_underscores, renamed)
+    }
+}
+
 pub fn first_visibility(visibilities: &[Option<&syn::Visibility>]) -> proc_macro2::TokenStream {
     let vis = visibilities
         .iter()
@@ -101,8 +265,32 @@ pub fn first_visibility(visibilities: &[Option<&syn::Visibility>]) -> proc_macro
     vis.to_token_stream()
 }
 
-pub fn public_visibility() -> syn::Visibility {
-    syn::Visibility::Public(syn::token::Pub::default())
+/// The visibility an item in a child module needs in order to be visible wherever it would be with
+/// `vis` in the parent module - e.g. `pub(super)` for a private item.
+pub fn visibility_from_submodule(vis: &syn::Visibility) -> syn::Visibility {
+    match vis {
+        syn::Visibility::Public(_) => vis.clone(),
+        syn::Visibility::Inherited => syn::parse_quote!(pub(super)),
+        syn::Visibility::Restricted(restricted) => {
+            let path = &restricted.path;
+            let mut segments = path.segments.iter();
+            match segments.next() {
+                // `self` of the parent module is `super` of the child.
+                Some(first) if path.leading_colon.is_none() && first.ident == "self" => {
+                    if path.segments.len() == 1 {
+                        syn::parse_quote!(pub(super))
+                    } else {
+                        syn::parse_quote!(pub(in super #( :: #segments )*))
+                    }
+                }
+                Some(first) if path.leading_colon.is_none() && first.ident == "super" => {
+                    syn::parse_quote!(pub(in super::#path))
+                }
+                // Paths from the crate root mean the same in any module.
+                _ => vis.clone(),
+            }
+        }
+    }
 }
 
 pub fn apply_subsections(
@@ -113,8 +301,8 @@ pub fn apply_subsections(
         return Err(syn::Error::new_spanned(list, "Expected builder(…)"));
     }
 
-    let parser = syn::punctuated::Punctuated::<_, syn::token::Comma>::parse_terminated;
-    let exprs = parser.parse2(list.tokens.clone())?;
+    let parser = syn::punctuated::Punctuated::<_, syn::token::Comma>::parse_terminated_with;
+    let exprs = (|input: ParseStream| parser(input, parse_subsection)).parse2(list.tokens.clone())?;
     for expr in exprs {
         applier(expr)?;
     }
@@ -122,12 +310,429 @@ pub fn apply_subsections(
     Ok(())
 }
 
+/// Subsections whose arguments are items or where predicates rather than expressions (e.g.
+/// `mutators(fn ...)`, `extra_impl { fn ... }` and `bounds(T: Clone)`) are passed on as verbatim
+/// tokens. The arguments of `build_method(...)` are parsed the same way, since they can include
+/// `bounds(...)`.
+fn parse_subsection(input: ParseStream) -> Result<syn::Expr, Error> {
+    let name = if input.peek(syn::Ident) {
+        input.fork().parse::<syn::Ident>()?.to_string()
+    } else {
+        String::new()
+    };
+    if input.peek2(syn::token::Paren) && (name == "mutators" || name == "bounds") {
+        let name: syn::Ident = input.parse()?;
+        let content;
+        let paren = syn::parenthesized!(content in input);
+        let mut tokens = name.into_token_stream();
+        paren.surround(&mut tokens, |tokens| {
+            tokens.extend(content.parse::<proc_macro2::TokenStream>())
+        });
+        return Ok(syn::Expr::Verbatim(tokens));
+    }
+    if input.peek2(syn::token::Brace) && name == "extra_impl" {
+        let name: syn::Ident = input.parse()?;
+        let content;
+        let brace = syn::braced!(content in input);
+        let mut tokens = name.into_token_stream();
+        brace.surround(&mut tokens, |tokens| {
+            tokens.extend(content.parse::<proc_macro2::TokenStream>())
+        });
+        return Ok(syn::Expr::Verbatim(tokens));
+    }
+    if input.peek2(syn::token::Paren) && name == "build_method" {
+        let name: syn::Ident = input.parse()?;
+        let content;
+        let paren_token = syn::parenthesized!(content in input);
+        return Ok(syn::Expr::Call(syn::ExprCall {
+            attrs: Vec::new(),
+            func: Box::new(syn::Expr::Path(syn::ExprPath {
+                attrs: Vec::new(),
+                qself: None,
+                path: name.into(),
+            })),
+            paren_token,
+            args: syn::punctuated::Punctuated::parse_terminated_with(&content, parse_subsection)?,
+        }));
+    }
+    input.parse()
+}
+
+/// Parses a `bounds(...)` group - the where predicates in it.
+pub fn parse_bounds(input: ParseStream) -> Result<Vec<syn::WherePredicate>, Error> {
+    let name: syn::Ident = input.parse()?;
+    if name != "bounds" {
+        return Err(Error::new_spanned(name, "Expected bounds(...)"));
+    }
+    let content;
+    syn::parenthesized!(content in input);
+    let predicates = syn::punctuated::Punctuated::<syn::WherePredicate, syn::token::Comma>::parse_terminated(&content)?;
+    Ok(predicates.into_iter().collect())
+}
+
+/// Parses an `extra_impl { ... }` group - the items of an `impl` block.
+pub fn parse_extra_impl(input: ParseStream) -> Result<Vec<syn::ImplItem>, Error> {
+    let name: syn::Ident = input.parse()?;
+    if name != "extra_impl" {
+        return Err(Error::new_spanned(name, "Expected extra_impl { ... }"));
+    }
+    let content;
+    syn::braced!(content in input);
+    let mut items = Vec::new();
+    while !content.is_empty() {
+        items.push(content.parse()?);
+    }
+    Ok(items)
+}
+
+/// Errors if a setting that takes a single value was already given one, e.g. in an earlier
+/// `#[builder(...)]` attribute.
+pub fn ensure_not_set<T>(setting: &Option<T>, key: &syn::Expr) -> Result<(), Error> {
+    if setting.is_some() {
+        return Err(Error::new_spanned(
+            key,
+            format!("Illegal setting - `{}` is already set", key.to_token_stream()),
+        ));
+    }
+    Ok(())
+}
+
+/// Error for an unrecognized key in a `#[builder(...)]` attribute, pointing at the key itself.
+pub fn unknown_key_error(key: &impl ToTokens, valid_keys: &[&str]) -> Error {
+    Error::new_spanned(
+        key,
+        format!(
+            "Unknown parameter `{}` - expected one of: {}",
+            key.to_token_stream(),
+            valid_keys.join(", ")
+        ),
+    )
+}
+
 pub fn expr_to_lit_string(expr: &syn::Expr) -> Result<String, Error> {
     match expr {
         syn::Expr::Lit(lit) => match &lit.lit {
             syn::Lit::Str(str) => Ok(str.value()),
-            _ => return Err(Error::new_spanned(expr, "attribute only allows str values")),
+            _ => Err(Error::new_spanned(expr, "attribute only allows str values")),
         },
-        _ => return Err(Error::new_spanned(expr, "attribute only allows str values")),
+        _ => Err(Error::new_spanned(expr, "attribute only allows str values")),
+    }
+}
+
+/// Collects the identifiers in the expression that may refer to variables from outside of it.
+/// Names bound inside the expression - closure parameters, `let` bindings, patterns of `match`
+/// arms, `for` loops and `if let`s, and nested items - are not included where they are in scope.
+pub fn collect_variable_idents(expr: &syn::Expr, result: &mut Vec<syn::Ident>) {
+    VariableCollector {
+        scopes: Vec::new(),
+        result,
+    }
+    .visit_expr(expr);
+}
+
+struct VariableCollector<'a> {
+    /// The names bound by the enclosing closures, blocks and patterns, innermost last.
+    scopes: Vec<Vec<syn::Ident>>,
+    result: &'a mut Vec<syn::Ident>,
+}
+
+impl VariableCollector<'_> {
+    fn add(&mut self, ident: &syn::Ident) {
+        if !self.scopes.iter().flatten().any(|bound| bound == ident) && !self.result.contains(ident) {
+            self.result.push(ident.clone());
+        }
+    }
+
+    fn bind(&mut self, pat: &syn::Pat) {
+        struct Bindings(Vec<syn::Ident>);
+        impl<'ast> Visit<'ast> for Bindings {
+            fn visit_pat_ident(&mut self, pat_ident: &'ast syn::PatIdent) {
+                self.0.push(pat_ident.ident.clone());
+                visit::visit_pat_ident(self, pat_ident);
+            }
+        }
+        let mut bindings = Bindings(Vec::new());
+        bindings.visit_pat(pat);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.extend(bindings.0);
+        }
+    }
+
+    fn in_scope(&mut self, bound: Vec<syn::Ident>, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(bound);
+        f(self);
+        self.scopes.pop();
+    }
+}
+
+impl<'ast> Visit<'ast> for VariableCollector<'_> {
+    fn visit_expr_path(&mut self, expr_path: &'ast syn::ExprPath) {
+        if expr_path.qself.is_none() {
+            if let Some(ident) = expr_path.path.get_ident() {
+                self.add(ident);
+            }
+        }
+        visit::visit_expr_path(self, expr_path);
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast syn::ExprClosure) {
+        self.in_scope(Vec::new(), |this| {
+            for input in &closure.inputs {
+                this.bind(input);
+            }
+            this.visit_expr(&closure.body);
+        });
+    }
+
+    fn visit_block(&mut self, block: &'ast syn::Block) {
+        // Items are visible in the whole block, even before they are declared.
+        let items = block
+            .stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                syn::Stmt::Item(item) => item_ident(item).cloned(),
+                _ => None,
+            })
+            .collect();
+        self.in_scope(items, |this| {
+            for stmt in &block.stmts {
+                match stmt {
+                    syn::Stmt::Local(local) => {
+                        if let Some(init) = &local.init {
+                            this.visit_local_init(init);
+                        }
+                        this.bind(&local.pat);
+                    }
+                    syn::Stmt::Item(_) => {}
+                    _ => this.visit_stmt(stmt),
+                }
+            }
+        });
+    }
+
+    fn visit_arm(&mut self, arm: &'ast syn::Arm) {
+        self.in_scope(Vec::new(), |this| {
+            this.bind(&arm.pat);
+            if let Some((_, guard)) = &arm.guard {
+                this.visit_expr(guard);
+            }
+            this.visit_expr(&arm.body);
+        });
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast syn::ExprForLoop) {
+        self.visit_expr(&for_loop.expr);
+        self.in_scope(Vec::new(), |this| {
+            this.bind(&for_loop.pat);
+            this.visit_block(&for_loop.body);
+        });
+    }
+
+    fn visit_expr_if(&mut self, expr_if: &'ast syn::ExprIf) {
+        // The bindings of an `if let` are in scope in the `then` branch only.
+        self.in_scope(Vec::new(), |this| {
+            this.visit_expr(&expr_if.cond);
+            this.visit_block(&expr_if.then_branch);
+        });
+        if let Some((_, else_branch)) = &expr_if.else_branch {
+            self.visit_expr(else_branch);
+        }
+    }
+
+    fn visit_expr_while(&mut self, expr_while: &'ast syn::ExprWhile) {
+        self.in_scope(Vec::new(), |this| {
+            this.visit_expr(&expr_while.cond);
+            this.visit_block(&expr_while.body);
+        });
+    }
+
+    fn visit_expr_let(&mut self, expr_let: &'ast syn::ExprLet) {
+        self.visit_expr(&expr_let.expr);
+        self.bind(&expr_let.pat);
+    }
+
+    fn visit_item(&mut self, _: &'ast syn::Item) {
+        // Items can't refer to the variables around them.
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Most macros that take expressions (e.g. `vec!` or `format!`) separate them with `,` or `;`.
+        // The tokens of other macros are scanned for anything that may refer to a variable.
+        let parser = syn::punctuated::Punctuated::<syn::Expr, MacroSeparator>::parse_terminated;
+        if let Ok(exprs) = mac.parse_body_with(parser) {
+            for expr in &exprs {
+                self.visit_expr(expr);
+            }
+        } else {
+            let mut idents = Vec::new();
+            collect_idents_in_tokens(mac.tokens.clone(), &mut idents);
+            for ident in &idents {
+                self.add(ident);
+            }
+        }
+    }
+}
+
+struct MacroSeparator;
+
+impl syn::parse::Parse for MacroSeparator {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::Token![;]) {
+            input.parse::<syn::Token![;]>()?;
+        } else {
+            input.parse::<syn::Token![,]>()?;
+        }
+        Ok(MacroSeparator)
+    }
+}
+
+fn item_ident(item: &syn::Item) -> Option<&syn::Ident> {
+    match item {
+        syn::Item::Const(item) => Some(&item.ident),
+        syn::Item::Enum(item) => Some(&item.ident),
+        syn::Item::Fn(item) => Some(&item.sig.ident),
+        syn::Item::Static(item) => Some(&item.ident),
+        syn::Item::Struct(item) => Some(&item.ident),
+        syn::Item::Union(item) => Some(&item.ident),
+        _ => None,
+    }
+}
+
+/// Collects the identifiers in the token stream that may refer to variables. This skips the ones
+/// that are method or field names (after `.`), path segments (after `::`), struct literal field
+/// names (before a single `:`) and lifetimes.
+fn collect_idents_in_tokens(tokens: TokenStream, result: &mut Vec<syn::Ident>) {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut previous_punct: Option<proc_macro2::Punct> = None;
+    let mut skip_next_ident = false;
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(group) => {
+                collect_idents_in_tokens(group.stream(), result);
+                skip_next_ident = false;
+            }
+            TokenTree::Ident(ident) => {
+                let is_field_name = matches!(
+                    tokens.peek(),
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == ':' && punct.spacing() == Spacing::Alone
+                ) && !matches!(&previous_punct, Some(punct) if punct.as_char() == ':');
+                if !skip_next_ident && !is_field_name && !result.contains(&ident) {
+                    result.push(ident);
+                }
+                skip_next_ident = false;
+            }
+            TokenTree::Punct(punct) => {
+                let joined_to_previous = matches!(&previous_punct, Some(previous) if previous.spacing() == Spacing::Joint && previous.as_char() == punct.as_char());
+                skip_next_ident = match punct.as_char() {
+                    // `.` but not `..`
+                    '.' => punct.spacing() == Spacing::Alone && !joined_to_previous,
+                    // The second `:` of `::`
+                    ':' => joined_to_previous,
+                    '\'' => true,
+                    _ => false,
+                };
+                previous_punct = Some(punct);
+                continue;
+            }
+            TokenTree::Literal(_) => {
+                skip_next_ident = false;
+            }
+        }
+        previous_punct = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variables(expr: syn::Expr) -> Vec<String> {
+        let mut idents = Vec::new();
+        collect_variable_idents(&expr, &mut idents);
+        idents.iter().map(ToString::to_string).collect()
+    }
+
+    fn wrapped(ty: syn::Type, wrapper: &str) -> Option<String> {
+        extract_wrapped_type(&ty, wrapper).map(|ty| ty.to_token_stream().to_string())
+    }
+
+    #[test]
+    fn test_extract_wrapped_type() {
+        assert_eq!(wrapped(syn::parse_quote!(Option<i32>), "Option"), Some("i32".to_owned()));
+        assert_eq!(
+            wrapped(syn::parse_quote!(Vec<Option<i32>>), "Vec"),
+            Some("Option < i32 >".to_owned())
+        );
+        assert_eq!(
+            wrapped(syn::parse_quote!(HashMap<String, i32>), "HashMap"),
+            Some("String".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_extract_wrapped_type_with_paths() {
+        assert_eq!(
+            wrapped(syn::parse_quote!(core::option::Option<i32>), "Option"),
+            Some("i32".to_owned())
+        );
+        assert_eq!(
+            wrapped(syn::parse_quote!(::std::option::Option<i32>), "Option"),
+            Some("i32".to_owned())
+        );
+        assert_eq!(wrapped(syn::parse_quote!((Option<i32>)), "Option"), Some("i32".to_owned()));
+    }
+
+    #[test]
+    fn test_extract_wrapped_type_mismatch() {
+        assert_eq!(wrapped(syn::parse_quote!(Vec<i32>), "Option"), None);
+        assert_eq!(wrapped(syn::parse_quote!(Option), "Option"), None);
+        assert_eq!(wrapped(syn::parse_quote!(Option<'a>), "Option"), None);
+        assert_eq!(wrapped(syn::parse_quote!(Option::<i32>::Inner), "Option"), None);
+        assert_eq!(wrapped(syn::parse_quote!(<T as Trait>::Option<i32>), "Option"), None);
+        assert_eq!(wrapped(syn::parse_quote!(&Option<i32>), "Option"), None);
+        assert_eq!(wrapped(syn::parse_quote!(MyOption<i32>), "Option"), None);
+    }
+
+    #[test]
+    fn test_collect_variable_idents() {
+        assert_eq!(variables(syn::parse_quote!(x + y.len() + z::w(a::b))), ["x", "y"]);
+        assert_eq!(variables(syn::parse_quote!(Point { x, y: z.y })), ["x", "z"]);
+        assert_eq!(variables(syn::parse_quote!(vec![x; n])), ["x", "n"]);
+        assert_eq!(variables(syn::parse_quote!(format!("{}", x))), ["x"]);
+        assert_eq!(variables(syn::parse_quote!(self.x)), ["self"]);
+    }
+
+    #[test]
+    fn test_collect_variable_idents_skips_bindings() {
+        assert_eq!(variables(syn::parse_quote!(v.iter().map(|x| x + y))), ["v", "y"]);
+        assert_eq!(
+            variables(syn::parse_quote!({
+                let x = x + 1;
+                x * y
+            })),
+            ["x", "y"]
+        );
+        assert_eq!(
+            variables(syn::parse_quote!({
+                fn x() {}
+                x()
+            })),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            variables(syn::parse_quote!(match a {
+                Some(x) => x,
+                None => b,
+            })),
+            ["a", "b"]
+        );
+        assert_eq!(variables(syn::parse_quote!(if let Some(x) = a { x } else { x })), ["a", "x"]);
+        assert_eq!(
+            variables(syn::parse_quote!(for x in a {
+                x;
+            })),
+            ["a"]
+        );
+        assert_eq!(variables(syn::parse_quote!(vec![v.iter().map(|x| x)])), ["v"]);
     }
 }